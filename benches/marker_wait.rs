@@ -0,0 +1,74 @@
+//! Per-call cost of a tiny `saxpy` chained through a one-event wait list,
+//! where the wait-list marker dominates the routine itself.
+//!
+//! `cargo bench --bench marker_wait` prints time and heap allocations per
+//! call (the allocations include whatever `ocl` and the driver do).
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use clblast_binding::Offset;
+use ocl::Buffer;
+
+/// Counts heap allocations made by this process.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+  unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    unsafe { System.alloc(layout) }
+  }
+
+  unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+    unsafe { System.dealloc(ptr, layout) }
+  }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+const N: usize = 16;
+const WARMUP: usize = 100;
+const ITERS: usize = 10_000;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+  let queue = clblast_binding::default_queue()?;
+  let x = Buffer::<f32>::builder()
+    .queue(queue.clone())
+    .len(N)
+    .fill_val(1.0)
+    .build()?;
+  let y = Buffer::<f32>::builder()
+    .queue(queue.clone())
+    .len(N)
+    .fill_val(0.0)
+    .build()?;
+
+  let mut prev = clblast_binding::saxpy(&queue, N, 1.0, &x, Offset(0), 1, &y, Offset(0), 1, &[])?
+    .expect("saxpy returned no event");
+  for _ in 0..WARMUP {
+    prev = clblast_binding::saxpy(&queue, N, 1.0, &x, Offset(0), 1, &y, Offset(0), 1, [&prev])?
+      .expect("saxpy returned no event");
+  }
+  prev.wait_for()?;
+
+  let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+  let start = Instant::now();
+  for _ in 0..ITERS {
+    prev = clblast_binding::saxpy(&queue, N, 1.0, &x, Offset(0), 1, &y, Offset(0), 1, [&prev])?
+      .expect("saxpy returned no event");
+  }
+  prev.wait_for()?;
+  let elapsed = start.elapsed();
+  let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations;
+
+  println!(
+    "saxpy n={N}, 1-event wait list: {:.2} us/call, {:.2} allocations/call",
+    elapsed.as_secs_f64() * 1e6 / ITERS as f64,
+    allocations as f64 / ITERS as f64,
+  );
+  Ok(())
+}
@@ -1,8 +1,11 @@
 //
 // This build script can either use prebuilt static files in `src/`
-// or (when the `generate-bindings` feature is enabled, or when no
-// static file exists) regenerate bindgen bindings and ocl-friendly
-// wrappers, writing results to both OUT_DIR and `src/`.
+// or (when the `generate-bindings` feature is enabled, when no static
+// file exists, or with CLBLAST_REFRESH_WRAPPERS=1) regenerate bindgen
+// bindings and ocl-friendly wrappers into OUT_DIR, which lib.rs then
+// includes. Nothing is written into `src/` unless CLBLAST_WRITE_SRC=1 is
+// set or the `generate-bindings` feature is on.
+// On docs.rs (DOCS_RS set) nothing is built, generated or linked.
 //
 // Public crate: keep logs concise and avoid non-portable assumptions.
 use std::{
@@ -11,10 +14,9 @@ use std::{
   process::Command,
 };
 
-use bindgen::callbacks::{EnumVariantValue, ParseCallbacks};
+use bindgen::callbacks::{AttributeInfo, EnumVariantValue, ParseCallbacks, TypeKind};
 
 /// Recursively copy a directory tree (mkdir -p + file copy).
-
 fn copy_dir(src: &Path, dst: &Path) -> io::Result<()> {
   if !dst.exists() {
     fs::create_dir_all(dst)?;
@@ -32,42 +34,186 @@ fn copy_dir(src: &Path, dst: &Path) -> io::Result<()> {
   Ok(())
 }
 
-/// Pretty-format a Rust file using `prettyplease`; ignore errors.
+/// Parse a `major.minor.patch` version string (extra components are ignored).
+fn parse_version(s: &str) -> Option<(u32, u32, u32)> {
+  let mut it = s.trim().split('.').map(|p| p.parse::<u32>().ok());
+  Some((it.next()??, it.next()??, it.next().flatten().unwrap_or(0)))
+}
 
-fn format_rs_file(path: &Path) {
-  if let Ok(status) = Command::new("rustfmt")
-    .arg("--edition")
-    .arg("2021")
-    .arg("--color")
-    .arg("never")
-    .arg(path)
-    .status()
-  {
-    if status.success() {
-      return;
+/// Read `clblast_VERSION_{MAJOR,MINOR,PATCH}` from CLBlast's top-level CMakeLists.txt.
+fn clblast_version_from_cmake(src: &Path) -> Option<(u32, u32, u32)> {
+  let text = fs::read_to_string(src.join("CMakeLists.txt")).ok()?;
+  let field = |name: &str| -> Option<u32> {
+    let key = format!("set(clblast_VERSION_{name}");
+    let line = text.lines().map(str::trim).find(|l| l.starts_with(&key))?;
+    line[key.len()..].trim_end_matches(')').trim().parse().ok()
+  };
+  Some((field("MAJOR")?, field("MINOR")?, field("PATCH")?))
+}
+
+/// Best-effort check that the C++ runtime CLBlast links against is installed.
+/// Emits a `cargo:warning` with an install hint instead of failing the build.
+fn probe_cxx_runtime(target: &str) {
+  if target.contains("apple") {
+    // libc++ ships with the Xcode command line tools.
+    let ok = Command::new("xcode-select")
+      .arg("-p")
+      .output()
+      .map(|o| o.status.success())
+      .unwrap_or(false);
+    if !ok {
+      println!(
+        "cargo:warning=libc++ not found: install the Xcode command line tools (`xcode-select --install`)"
+      );
     }
+    return;
+  }
+
+  let lib = if target.contains("windows") {
+    "libstdc++.a"
+  } else {
+    "libstdc++.so"
+  };
+  let cxx = env::var("CXX").unwrap_or_else(|_| "c++".to_string());
+  // `-print-file-name` echoes the bare name back when the library is not found.
+  let found = Command::new(&cxx)
+    .arg(format!("-print-file-name={lib}"))
+    .output()
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| Path::new(String::from_utf8_lossy(&o.stdout).trim()).is_absolute());
+  match found {
+    Some(true) => {}
+    Some(false) => println!(
+      "cargo:warning={lib} not found: install the C++ standard library development files \
+       (e.g. `libstdc++-dev` on Debian/Ubuntu, `libstdc++-devel` on Fedora)"
+    ),
+    None => println!(
+      "cargo:warning=could not run `{cxx}` to locate {lib}; if linking fails with undefined C++ \
+       symbols, install a C++ toolchain and the standard library development files"
+    ),
+  }
+}
+
+/// Emit the `CLBLAST_VERSION_*` env vars read by `src/version.rs`.
+fn emit_clblast_version(version: Option<(u32, u32, u32)>) {
+  let (major, minor, patch) = version.unwrap_or_else(|| {
+    println!("cargo:warning=could not determine the CLBlast version; CLBLAST_VERSION is 0.0.0");
+    (0, 0, 0)
+  });
+  println!("cargo:rustc-env=CLBLAST_VERSION_MAJOR={major}");
+  println!("cargo:rustc-env=CLBLAST_VERSION_MINOR={minor}");
+  println!("cargo:rustc-env=CLBLAST_VERSION_PATCH={patch}");
+}
+
+/// `Version:` field of a pkg-config `.pc` file, if the file exists.
+fn pc_file_version(pc: &Path) -> Option<(u32, u32, u32)> {
+  fs::read_to_string(pc)
+    .ok()?
+    .lines()
+    .find_map(|l| l.strip_prefix("Version:"))
+    .and_then(|v| parse_version(v.trim()))
+}
+
+/// Install prefix of Homebrew's `clblast` keg: `$HOMEBREW_PREFIX/opt/clblast`,
+/// else whatever `brew --prefix clblast` prints. `None` unless the prefix
+/// contains `include/clblast_c.h`.
+fn homebrew_clblast_prefix() -> Option<PathBuf> {
+  println!("cargo:rerun-if-env-changed=HOMEBREW_PREFIX");
+  let has_header = |p: &PathBuf| p.join("include/clblast_c.h").is_file();
+  if let Some(prefix) = env::var_os("HOMEBREW_PREFIX")
+    .map(|p| PathBuf::from(p).join("opt/clblast"))
+    .filter(has_header)
+  {
+    return Some(prefix);
   }
+  let out = Command::new("brew")
+    .args(["--prefix", "clblast"])
+    .output()
+    .ok()
+    .filter(|o| o.status.success())?;
+  Some(PathBuf::from(String::from_utf8(out.stdout).ok()?.trim())).filter(has_header)
+}
 
+/// Whether regenerated files are copied back into `src/`.
+///
+/// Only when asked for, with `CLBLAST_WRITE_SRC=1` or the `generate-bindings`
+/// feature; never with `CLBLAST_NO_SRC_WRITEBACK=1`, or when the crate is
+/// built from a cargo registry / git checkout (`~/.cargo/registry`,
+/// `~/.cargo/git`), which should be treated as read-only.
+fn src_writeback_enabled(f_gen: bool) -> bool {
+  if env::var("CLBLAST_NO_SRC_WRITEBACK").ok().as_deref() == Some("1") {
+    return false;
+  }
+  if !f_gen && env::var("CLBLAST_WRITE_SRC").ok().as_deref() != Some("1") {
+    return false;
+  }
+  let manifest = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+  let components: Vec<_> = Path::new(&manifest)
+    .components()
+    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+    .collect();
+  !components
+    .windows(2)
+    .any(|w| w[0] == ".cargo" && (w[1] == "registry" || w[1] == "git"))
+}
+
+/// Pretty-format a generated Rust file; ignore errors.
+///
+/// `prettyplease` always runs, so regeneration does not depend on the
+/// `rustfmt` component; `rustfmt` is applied on top when it is installed.
+/// Leading `//` comments, which `syn` drops, are kept.
+fn format_rs_file(path: &Path) {
   if let Ok(src) = fs::read_to_string(path) {
     if let Ok(file) = syn::parse_file(&src) {
+      let header: String = src
+        .lines()
+        .take_while(|l| l.starts_with("//"))
+        .map(|l| format!("{l}\n"))
+        .collect();
+      let sep = if header.is_empty() { "" } else { "\n" };
       let pretty = prettyplease::unparse(&file);
-      let _ = fs::write(path, pretty);
+      let _ = fs::write(path, format!("{header}{sep}{pretty}"));
     }
   }
+
+  let _ = Command::new("rustfmt")
+    .arg("--edition")
+    .arg("2021")
+    .arg("--color")
+    .arg("never")
+    .arg(path)
+    .status();
 }
 
 fn main() {
   let target = env::var("TARGET").expect("TARGET not set");
+  println!("cargo:rustc-check-cfg=cfg(clblast_out_dir)");
+  println!("cargo:rustc-check-cfg=cfg(clblast_stale_wrappers)");
+
+  // docs.rs has no network, CMake or OpenCL: skip building, bindgen and
+  // linking entirely. lib.rs then includes the committed files in `src/`.
+  println!("cargo:rerun-if-env-changed=DOCS_RS");
+  if env::var_os("DOCS_RS").is_some() {
+    emit_clblast_version(clblast_version_from_cmake(Path::new("vendor/clblast")));
+    return;
+  }
 
   let f_v_clb = env::var("CARGO_FEATURE_VENDORED_CLBLAST").is_ok();
   let f_s_clb = env::var("CARGO_FEATURE_SYSTEM_CLBLAST").is_ok();
   let f_v_ocl = env::var("CARGO_FEATURE_VENDORED_OPENCL_HEADERS").is_ok();
   let f_s_ocl = env::var("CARGO_FEATURE_SYSTEM_OPENCL_HEADERS").is_ok();
   let f_gen = env::var("CARGO_FEATURE_GENERATE_BINDINGS").is_ok();
+  let f_prebuilt = env::var("CARGO_FEATURE_PREBUILT_ONLY").is_ok();
 
   if f_v_clb && f_s_clb {
     panic!("features 'vendored-clblast' and 'system-clblast' are mutually exclusive");
   }
+  if f_prebuilt && (f_v_clb || f_gen) {
+    panic!(
+      "feature 'prebuilt-only' cannot be combined with 'vendored-clblast' or 'generate-bindings'"
+    );
+  }
   if f_v_ocl && f_s_ocl {
     panic!("features 'vendored-opencl-headers' and 'system-opencl-headers' are mutually exclusive");
   }
@@ -81,6 +227,17 @@ fn main() {
     .map(PathBuf::from)
     .unwrap_or(vendor_root.join("opencl_headers"));
 
+  // Optional explicit OpenCL loader (e.g. /opt/icd/lib/libOpenCL.so.1), Unix only.
+  println!("cargo:rerun-if-env-changed=OPENCL_LOADER");
+  let opencl_loader = env::var("OPENCL_LOADER").ok().map(PathBuf::from);
+  if let Some(loader) = &opencl_loader {
+    if target.contains("apple") || target.contains("windows") {
+      println!("cargo:warning=OPENCL_LOADER is only honoured on Linux/Unix targets; ignoring it");
+    } else if !loader.is_file() {
+      panic!("OPENCL_LOADER={:?} does not point to a file", loader);
+    }
+  }
+
   let out = PathBuf::from(env::var("OUT_DIR").unwrap());
   let shim_root = out.join("sdkshims");
   let shim_opencl = shim_root.join("OpenCL");
@@ -103,7 +260,42 @@ fn main() {
   }
 
   let clblast_header: PathBuf;
-  if f_s_clb {
+  let clblast_version: Option<(u32, u32, u32)>;
+  if f_prebuilt {
+    // Hermetic builds: no CMake, no bindgen, no rustfmt, no writes to src/.
+    println!("cargo:info=Using prebuilt CLBlast from CLBLAST_LIB_DIR/CLBLAST_INCLUDE_DIR");
+    println!("cargo:rerun-if-env-changed=CLBLAST_LIB_DIR");
+    println!("cargo:rerun-if-env-changed=CLBLAST_INCLUDE_DIR");
+    let lib_dir = PathBuf::from(
+      env::var_os("CLBLAST_LIB_DIR").expect("prebuilt-only: CLBLAST_LIB_DIR is not set"),
+    );
+    let include_dir = PathBuf::from(
+      env::var_os("CLBLAST_INCLUDE_DIR").expect("prebuilt-only: CLBLAST_INCLUDE_DIR is not set"),
+    );
+    if !lib_dir.is_dir() {
+      panic!(
+        "prebuilt-only: CLBLAST_LIB_DIR={:?} is not a directory",
+        lib_dir
+      );
+    }
+    for generated in ["bindings_static.rs", "clblast_ocl_wrap.rs"] {
+      let path = Path::new("src").join(generated);
+      if !path.is_file() {
+        panic!(
+          "prebuilt-only: {:?} is missing and will not be regenerated",
+          path
+        );
+      }
+      println!("cargo:rerun-if-changed={}", path.display());
+    }
+    println!("cargo:rustc-link-search=native={}", lib_dir.display());
+    println!("cargo:rustc-link-lib=dylib=clblast");
+    clblast_header = include_dir.join("clblast_c.h");
+    clblast_version = pc_file_version(&lib_dir.join("pkgconfig/clblast.pc"));
+    if !clblast_header.exists() {
+      panic!("clblast_c.h not found at {:?}", clblast_header);
+    }
+  } else if f_s_clb {
     println!("cargo:info=Using system CLBlast (dynamic)");
     if target.contains("windows") {
       let lib = vcpkg::find_package("clblast")
@@ -113,16 +305,40 @@ fn main() {
         .get(0)
         .expect("vcpkg: include path missing")
         .join("clblast_c.h");
+      clblast_version = None;
     } else {
-      let lib = pkg_config::Config::new()
+      match pkg_config::Config::new()
         .atleast_version("1.5")
         .probe("clblast")
-        .expect("pkg-config: CLBlast not found (install libclblast-dev or provide .pc)");
-      clblast_header = lib
-        .include_paths
-        .get(0)
-        .expect("pkg-config: include path missing")
-        .join("clblast_c.h");
+      {
+        Ok(lib) => {
+          clblast_header = lib
+            .include_paths
+            .get(0)
+            .expect("pkg-config: include path missing")
+            .join("clblast_c.h");
+          clblast_version = parse_version(&lib.version);
+        }
+        Err(e) => {
+          // Homebrew's keg is not on pkg-config's default search path.
+          let prefix = if target.contains("apple") {
+            homebrew_clblast_prefix()
+          } else {
+            None
+          };
+          let Some(prefix) = prefix else {
+            panic!("pkg-config: CLBlast not found (install libclblast-dev or provide .pc): {e}");
+          };
+          println!("cargo:info=Using Homebrew CLBlast at {}", prefix.display());
+          println!(
+            "cargo:rustc-link-search=native={}",
+            prefix.join("lib").display()
+          );
+          println!("cargo:rustc-link-lib=dylib=clblast");
+          clblast_header = prefix.join("include").join("clblast_c.h");
+          clblast_version = pc_file_version(&prefix.join("lib/pkgconfig/clblast.pc"));
+        }
+      }
     }
     if !clblast_header.exists() {
       panic!("clblast_c.h not found at {:?}", clblast_header);
@@ -131,6 +347,71 @@ fn main() {
     println!("cargo:info=Building bundled CLBlast (static)");
     let mut cfg = cmake::Config::new(&clblast_src);
     cfg.define("BUILD_SHARED_LIBS", "OFF");
+    // The cmake crate derives CMAKE_BUILD_TYPE from the cargo profile, which
+    // leaves CLBlast unoptimized in debug builds. Default to Release instead.
+    println!("cargo:rerun-if-env-changed=CLBLAST_CMAKE_BUILD_TYPE");
+    let build_type = env::var("CLBLAST_CMAKE_BUILD_TYPE").unwrap_or_else(|_| "Release".to_string());
+    cfg.profile(&build_type);
+    // Only the library is needed for linking; tuners can be re-enabled with
+    // CLBLAST_BUILD_TUNERS=1.
+    println!("cargo:rerun-if-env-changed=CLBLAST_BUILD_TUNERS");
+    let tuners = env::var("CLBLAST_BUILD_TUNERS").ok().as_deref() == Some("1");
+    cfg.define("TUNERS", if tuners { "ON" } else { "OFF" });
+    cfg.define("CLIENTS", "OFF");
+    cfg.define("TESTS", "OFF");
+    cfg.define("SAMPLES", "OFF");
+
+    // MSVC: the C runtime of CLBlast must match the Rust binary. Defaults to
+    // /MT with `+crt-static` and /MD otherwise; CLBLAST_MSVC_RUNTIME=MT|MD
+    // overrides.
+    println!("cargo:rerun-if-env-changed=CLBLAST_MSVC_RUNTIME");
+    if target.contains("msvc") {
+      let crt_static = env::var("CARGO_CFG_TARGET_FEATURE")
+        .map(|f| f.split(',').any(|f| f == "crt-static"))
+        .unwrap_or(false);
+      let static_crt = match env::var("CLBLAST_MSVC_RUNTIME").ok().as_deref() {
+        None => crt_static,
+        Some(v) if v.eq_ignore_ascii_case("mt") => true,
+        Some(v) if v.eq_ignore_ascii_case("md") => false,
+        Some(v) => panic!("CLBLAST_MSVC_RUNTIME={v:?}: expected `MT` or `MD`"),
+      };
+      cfg.define(
+        "OVERRIDE_MSVC_FLAGS_TO_MT",
+        if static_crt { "ON" } else { "OFF" },
+      );
+      cfg.static_crt(static_crt);
+    }
+
+    // Build parallelism independent of cargo's -j, and the CMake generator.
+    println!("cargo:rerun-if-env-changed=CLBLAST_BUILD_JOBS");
+    println!("cargo:rerun-if-env-changed=CMAKE_GENERATOR");
+    let generator = env::var("CMAKE_GENERATOR").ok();
+    if let Some(g) = &generator {
+      cfg.generator(g);
+    }
+    if let Ok(jobs) = env::var("CLBLAST_BUILD_JOBS") {
+      let jobs: usize = jobs
+        .parse()
+        .unwrap_or_else(|_| panic!("CLBLAST_BUILD_JOBS={jobs:?} is not a number"));
+      // cmake-rs picks a Visual Studio generator on MSVC unless told otherwise.
+      let msbuild = match &generator {
+        Some(g) => g.starts_with("Visual Studio"),
+        None => target.contains("msvc"),
+      };
+      if msbuild {
+        cfg.build_arg(format!("/m:{jobs}"));
+      } else {
+        cfg.build_arg(format!("-j{jobs}"));
+      }
+    }
+
+    // Extra C++ flags for CLBlast itself (e.g. `-mavx2`), whitespace separated.
+    println!("cargo:rerun-if-env-changed=CLBLAST_CXXFLAGS");
+    if let Ok(flags) = env::var("CLBLAST_CXXFLAGS") {
+      for flag in flags.split_whitespace() {
+        cfg.cxxflag(flag);
+      }
+    }
 
     cfg.define("OpenCL_INCLUDE_DIR", &shim_root);
 
@@ -139,6 +420,11 @@ fn main() {
         "OpenCL_LIBRARY",
         "/System/Library/Frameworks/OpenCL.framework/OpenCL",
       );
+    } else if let Some(loader) = opencl_loader
+      .as_ref()
+      .filter(|_| !target.contains("windows"))
+    {
+      cfg.define("OpenCL_LIBRARY", loader);
     } else if let Ok(libdir) = env::var("OPENCL_LIB_DIR") {
       cfg.define("OpenCL_LIBRARY", Path::new(&libdir));
     }
@@ -151,6 +437,15 @@ fn main() {
     if !clblast_header.exists() {
       panic!("clblast_c.h not found at {:?}", clblast_header);
     }
+    clblast_version = clblast_version_from_cmake(&clblast_src);
+  }
+
+  // ---- CLBlast version (exposed as `CLBLAST_VERSION`)----
+  emit_clblast_version(clblast_version);
+
+  // MSVC brings its own C++ runtime; everything else links stdc++/c++ below.
+  if !f_prebuilt && (!target.contains("windows") || target.contains("gnu")) {
+    probe_cxx_runtime(&target);
   }
 
   if target.contains("apple") {
@@ -165,13 +460,49 @@ fn main() {
     }
   } else {
     // Linux / other Unix
-    println!("cargo:rustc-link-lib=dylib=OpenCL");
+    if let Some(loader) = &opencl_loader {
+      // Link the given file verbatim so a versioned soname works without a symlink.
+      let dir = loader
+        .parent()
+        .expect("OPENCL_LOADER has no parent directory");
+      let file = loader
+        .file_name()
+        .expect("OPENCL_LOADER has no file name")
+        .to_string_lossy();
+      println!("cargo:rustc-link-search=native={}", dir.display());
+      println!("cargo:rustc-link-lib=dylib:+verbatim={file}");
+    } else {
+      println!("cargo:rustc-link-lib=dylib=OpenCL");
+    }
     println!("cargo:rustc-link-lib=dylib=stdc++");
   }
 
+  if f_prebuilt {
+    // lib.rs includes the committed files in `src/` verbatim.
+    check_committed_wrappers(
+      &PathBuf::from("src").join("clblast_ocl_wrap.rs"),
+      &clblast_header,
+      &out,
+    );
+    return;
+  }
+
   // ---- bindings (static or generated)----
+  println!("cargo:rerun-if-env-changed=CLBLAST_NO_SRC_WRITEBACK");
+  println!("cargo:rerun-if-env-changed=CLBLAST_WRITE_SRC");
+  println!("cargo:rerun-if-env-changed=CLBLAST_REFRESH_WRAPPERS");
+  let write_src = src_writeback_enabled(f_gen);
+
   let static_rs = PathBuf::from("src").join("bindings_static.rs");
+  let wrap_static = PathBuf::from("src").join("clblast_ocl_wrap.rs");
   let need_generate = f_gen || !static_rs.exists();
+  let regenerate = need_generate
+    || !wrap_static.exists()
+    || env::var("CLBLAST_REFRESH_WRAPPERS").ok().as_deref() == Some("1");
+  if regenerate {
+    // lib.rs includes the OUT_DIR copies instead of the files in `src/`.
+    println!("cargo:rustc-cfg=clblast_out_dir");
+  }
 
   let out_bind = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
 
@@ -187,9 +518,24 @@ fn main() {
       .size_t_is_usize(true)
       .rustified_enum("CLBlast.*")
       .prepend_enum_name(false)
-      .formatter(bindgen::Formatter::Rustfmt)
+      .derive_hash(true)
+      .derive_eq(true)
+      .derive_ord(true)
+      .formatter(bindgen::Formatter::Prettyplease)
       .parse_callbacks(Box::new(ClblastEnumTrim));
 
+    // Extra symbols (e.g. OpenCL enums for device queries), as
+    // comma-separated regexes matched against functions, types and vars.
+    println!("cargo:rerun-if-env-changed=CLBLAST_EXTRA_ALLOWLIST");
+    if let Ok(extra) = env::var("CLBLAST_EXTRA_ALLOWLIST") {
+      for pattern in extra.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+        b = b
+          .allowlist_function(pattern)
+          .allowlist_type(pattern)
+          .allowlist_var(pattern);
+      }
+    }
+
     // always prefer the shim_root (expose OpenCL/opencl.h)
     b = b.clang_arg(format!("-I{}", shim_root.display()));
 
@@ -223,7 +569,9 @@ fn main() {
       .expect("Couldn't write bindings.rs");
 
     // also write generated bindings back to static file (best-effort)
-    if let Err(e) =
+    if !write_src {
+      println!("cargo:info=Skipping write-back of src/bindings_static.rs");
+    } else if let Err(e) =
       fs::create_dir_all("src").and_then(|_| fs::copy(&out_bind, &static_rs).map(|_| ()))
     {
       eprintln!("cargo:warning=failed to write src/bindings_static.rs: {e}");
@@ -237,69 +585,120 @@ fn main() {
 
   // ---- autogen wrappers for all exported functions ----
 
-  let out_wrap_outdir = out.join("clblast_ocl_wrap.rs");
-  generate_ocl_wrappers(&out_bind, &out_wrap_outdir);
-  println!(
-    "cargo:warning=CLBlast wrappers: generated -> {}",
-    out_wrap_outdir.display()
-  );
+  // Regenerate into OUT_DIR when the bindings were regenerated, `src/` has
+  // no wrapper file, or CLBLAST_REFRESH_WRAPPERS=1; otherwise lib.rs
+  // includes the committed `src/clblast_ocl_wrap.rs` and nothing is written.
+  if regenerate {
+    let out_wrap_outdir = out.join("clblast_ocl_wrap.rs");
+    generate_ocl_wrappers(&out_bind, &out_wrap_outdir);
+    println!(
+      "cargo:warning=CLBlast wrappers: generated -> {}",
+      out_wrap_outdir.display()
+    );
 
-  format_rs_file(&out_wrap_outdir);
+    format_rs_file(&out_wrap_outdir);
 
-  let wrap_static = PathBuf::from("src").join("clblast_ocl_wrap.rs");
-  // Always rebuild wrappers in OUT_DIR. Decide how to propagate into src/:
-  // - If the "generate-bindings" feature is enabled, always overwrite the static file.
-  // - Otherwise, copy on first run; to refresh manually, set CLBLAST_REFRESH_WRAPPERS=1.
-  println!("cargo:rerun-if-env-changed=CLBLAST_REFRESH_WRAPPERS");
-  if f_gen {
-    // Overwrite unconditionally when the feature is enabled.
-    if let Err(e) =
-      fs::create_dir_all("src").and_then(|_| fs::copy(&out_wrap_outdir, &wrap_static).map(|_| ()))
-    {
-      eprintln!("cargo:warning=failed to write src/clblast_ocl_wrap.rs: {e}");
-    } else {
-      println!("cargo:info=Overwrote {}", wrap_static.display());
-    }
-  } else if !wrap_static.exists() {
-    if let Err(e) =
-      fs::create_dir_all("src").and_then(|_| fs::copy(&out_wrap_outdir, &wrap_static).map(|_| ()))
-    {
-      eprintln!("cargo:warning=failed to write src/clblast_ocl_wrap.rs: {e}");
-    } else {
+    if !write_src {
       println!(
-        "cargo:info=Initialized {} from {}",
-        wrap_static.display(),
-        out_wrap_outdir.display()
+        "cargo:info=Skipping write-back of {} (set CLBLAST_WRITE_SRC=1 to update it)",
+        wrap_static.display()
       );
-    }
-  } else if env::var("CLBLAST_REFRESH_WRAPPERS").ok().as_deref() == Some("1") {
-    match (
-      fs::read_to_string(&wrap_static),
-      fs::read_to_string(&out_wrap_outdir),
-    ) {
-      (Ok(old), Ok(new)) if old != new => {
-        if let Err(e) = fs::write(&wrap_static, new) {
-          eprintln!("cargo:warning=failed to update src/clblast_ocl_wrap.rs: {e}");
-        } else {
-          println!("cargo:info=Updated {}", wrap_static.display());
+    } else {
+      match (
+        fs::read_to_string(&wrap_static),
+        fs::read_to_string(&out_wrap_outdir),
+      ) {
+        (Ok(old), Ok(new)) if old == new => println!(
+          "cargo:info=No wrapper changes; kept {}",
+          wrap_static.display()
+        ),
+        _ => {
+          if let Err(e) = fs::create_dir_all("src")
+            .and_then(|_| fs::copy(&out_wrap_outdir, &wrap_static).map(|_| ()))
+          {
+            eprintln!("cargo:warning=failed to write src/clblast_ocl_wrap.rs: {e}");
+          } else {
+            println!("cargo:info=Updated {}", wrap_static.display());
+          }
         }
       }
-      _ => println!(
-        "cargo:info=No wrapper changes; kept {}",
-        wrap_static.display()
-      ),
     }
-  } else {
-    println!(
-      "cargo:info=Keeping existing {} (set CLBLAST_REFRESH_WRAPPERS=1 to refresh)",
-      wrap_static.display()
-    );
+    return;
   }
+
+  println!(
+    "cargo:info=Using committed {} (set CLBLAST_REFRESH_WRAPPERS=1 to regenerate)",
+    wrap_static.display()
+  );
+  println!("cargo:rerun-if-changed={}", wrap_static.display());
+
+  check_committed_wrappers(&wrap_static, &clblast_header, &out);
 }
 
-#[derive(Debug)]
-/// bindgen callback: trim `CLBlast<EnumName>` prefixes from enum variants.
+/// The committed `src/clblast_ocl_wrap.rs` (and `src/bindings_static.rs`)
+/// declare every routine of the bundled CLBlast, but a system or prebuilt
+/// one may be older. Compare the wrappers with the `clblast_c.h` actually
+/// being built or linked, and turn routines it lacks into one clear
+/// `compile_error!` instead of an undefined symbol at link time.
+fn check_committed_wrappers(wrap_static: &Path, clblast_header: &Path, out: &Path) {
+  println!("cargo:rerun-if-changed={}", clblast_header.display());
+  let wrappers = fs::read_to_string(wrap_static).unwrap_or_default();
+  let header = fs::read_to_string(clblast_header).expect("Couldn't read clblast_c.h");
+  let missing = missing_sys_routines(&wrappers, &header);
+  if missing.is_empty() {
+    return;
+  }
+  let errors: String = missing
+    .iter()
+    .map(|name| {
+      format!(
+        "compile_error!(\"src/clblast_ocl_wrap.rs calls `{name}`, which {} does not declare; \
+         build with the `generate-bindings` feature to regenerate the bindings and wrappers \
+         from it\");\n",
+        clblast_header.display().to_string().replace('\\', "/")
+      )
+    })
+    .collect();
+  fs::write(out.join("stale_wrappers.rs"), errors).expect("Couldn't write stale_wrappers.rs");
+  println!("cargo:rustc-cfg=clblast_stale_wrappers");
+  println!(
+    "cargo:warning={} calls routines missing from {}: {}",
+    wrap_static.display(),
+    clblast_header.display(),
+    missing.join(", ")
+  );
+}
+
+/// `CLBlast*` functions called as `sys::CLBlast*(` in `wrappers` whose name
+/// does not appear anywhere in `bindings` (Rust bindings or a C header),
+/// sorted and deduplicated.
+fn missing_sys_routines(wrappers: &str, bindings: &str) -> Vec<String> {
+  // The identifier at the start of `text` and what follows it.
+  fn split_ident(text: &str) -> (&str, &str) {
+    let end = text
+      .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+      .unwrap_or(text.len());
+    text.split_at(end)
+  }
+  let declared: std::collections::BTreeSet<&str> = bindings
+    .match_indices("CLBlast")
+    .map(|(i, _)| split_ident(&bindings[i..]).0)
+    .collect();
+  let called: std::collections::BTreeSet<&str> = wrappers
+    .match_indices("sys::CLBlast")
+    .map(|(i, _)| split_ident(&wrappers[i + "sys::".len()..]))
+    .filter(|(_, rest)| rest.starts_with('('))
+    .map(|(name, _)| name)
+    .collect();
+  called
+    .difference(&declared)
+    .map(|name| name.to_string())
+    .collect()
+}
 
+#[derive(Debug)]
+/// bindgen callback: trim `CLBlast<EnumName>` prefixes from enum variants,
+/// and derive serde's traits on the enums behind the `serde` feature.
 struct ClblastEnumTrim;
 
 impl ParseCallbacks for ClblastEnumTrim {
@@ -323,10 +722,22 @@ impl ParseCallbacks for ClblastEnumTrim {
     }
     None
   }
+
+  // An attribute rather than `add_derives`: the committed bindings are
+  // shared by every feature set, so the derive must stay behind `cfg_attr`.
+  fn add_attributes(&self, info: &AttributeInfo<'_>) -> Vec<String> {
+    if info.kind == TypeKind::Enum && info.name.starts_with("CLBlast") {
+      vec![
+        r#"#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]"#
+          .to_string(),
+      ]
+    } else {
+      vec![]
+    }
+  }
 }
 
 /// Parse bindgen output and emit ocl-friendly wrappers and constant re-exports.
-
 fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path::Path) {
   use heck::ToSnakeCase;
   use quote::{format_ident, quote};
@@ -335,9 +746,132 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
   let src = std::fs::read_to_string(bindings_rs).expect("read bindings.rs failed");
   let file: syn::File = syn::parse_file(&src).expect("parse bindgen output failed");
 
-  let mut const_exports: Vec<proc_macro2::TokenStream> = Vec::new();
-  let mut fn_wrappers: Vec<proc_macro2::TokenStream> = Vec::new();
+  // Keyed by item name and sorted before emitting, so regenerating against
+  // the same header yields a byte-identical file.
+  let mut const_exports: Vec<(String, proc_macro2::TokenStream)> = Vec::new();
+  let mut fn_wrappers: Vec<(String, proc_macro2::TokenStream)> = Vec::new();
   let mut wrapped_count = 0usize;
+  let mut status_codes: Vec<proc_macro2::TokenStream> = Vec::new();
+
+  /// Cargo feature gating the wrapper of `cname` (`level1`, `level2`,
+  /// `level3`, `extra` or `batched`), or `None` for the cache/tuning calls
+  /// and anything unrecognised.
+  fn routine_feature(cname: &str) -> Option<&'static str> {
+    let name = cname.trim_start_matches("CLBlast");
+    if matches!(name, "ClearCache" | "FillCache" | "OverrideParameters") {
+      return None;
+    }
+    // Drop the precision prefix: `S`/`D`/`C`/`Z`/`H`, or `iS`... for `iXamax`.
+    let name = name.strip_prefix('i').unwrap_or(name);
+    let base = name.get(1..)?.to_ascii_lowercase();
+    if base.ends_with("batched") {
+      return Some("batched");
+    }
+    match base.as_str() {
+      "rotg" | "rotmg" | "rot" | "rotm" | "swap" | "scal" | "copy" | "axpy" | "dot" | "dotu"
+      | "dotc" | "nrm2" | "asum" | "sum" | "amax" | "amin" | "max" | "min" | "cnrm2" | "znrm2"
+      | "casum" | "zasum" | "csum" | "zsum" => Some("level1"),
+      "gemv" | "gbmv" | "hemv" | "hbmv" | "hpmv" | "symv" | "sbmv" | "spmv" | "trmv" | "tbmv"
+      | "tpmv" | "trsv" | "tbsv" | "tpsv" | "ger" | "geru" | "gerc" | "her" | "hpr" | "her2"
+      | "hpr2" | "syr" | "spr" | "syr2" | "spr2" => Some("level2"),
+      "gemm" | "symm" | "hemm" | "syrk" | "herk" | "syr2k" | "her2k" | "trmm" | "trsm"
+      | "gemmwithtempbuffer" | "gemmtempbuffersize" => Some("level3"),
+      "had" | "omatcopy" | "im2col" | "col2im" | "convgemm" => Some("extra"),
+      _ => None,
+    }
+  }
+
+  /// One-line description of a routine for its wrapper's docs, keyed by
+  /// the name without precision prefix (as in `routine_feature`).
+  fn routine_summary(cname: &str) -> Option<&'static str> {
+    let name = cname.trim_start_matches("CLBlast");
+    match name {
+      "ClearCache" => return Some("drops every cached kernel and parameter"),
+      "FillCache" => return Some("compiles and caches every kernel for `device` ahead of time"),
+      "OverrideParameters" => {
+        return Some("replaces the tuning parameters of a kernel on `device`");
+      }
+      _ => {}
+    }
+    let name = name.strip_prefix('i').unwrap_or(name);
+    let summary = match name.get(1..)?.to_ascii_lowercase().as_str() {
+      "rotg" => "constructs a Givens plane rotation",
+      "rotmg" => "constructs a modified Givens plane rotation",
+      "rot" => "applies a Givens plane rotation",
+      "rotm" => "applies a modified Givens plane rotation",
+      "swap" => "swaps two vectors, `x <-> y`",
+      "scal" => "scales a vector, `x = alpha * x`",
+      "copy" => "copies a vector, `y = x`",
+      "axpy" => "computes `y = alpha * x + y`",
+      "dot" => "computes the dot product `x^T * y`",
+      "dotu" => "computes the unconjugated dot product `x^T * y`",
+      "dotc" => "computes the conjugated dot product `x^H * y`",
+      "nrm2" | "cnrm2" | "znrm2" => "computes the Euclidean norm `||x||_2`",
+      "asum" | "casum" | "zasum" => "computes the sum of absolute values of `x`",
+      "sum" | "csum" | "zsum" => "computes the sum of the elements of `x`",
+      "amax" => "finds the index of the element with the largest absolute value",
+      "amin" => "finds the index of the element with the smallest absolute value",
+      "max" => "finds the index of the largest element",
+      "min" => "finds the index of the smallest element",
+      "gemv" => "computes `y = alpha * op(A) * x + beta * y` for a general `A`",
+      "gbmv" => "computes `y = alpha * op(A) * x + beta * y` for a banded `A`",
+      "hemv" => "computes `y = alpha * A * x + beta * y` for a Hermitian `A`",
+      "hbmv" => "computes `y = alpha * A * x + beta * y` for a Hermitian banded `A`",
+      "hpmv" => "computes `y = alpha * A * x + beta * y` for a Hermitian packed `A`",
+      "symv" => "computes `y = alpha * A * x + beta * y` for a symmetric `A`",
+      "sbmv" => "computes `y = alpha * A * x + beta * y` for a symmetric banded `A`",
+      "spmv" => "computes `y = alpha * A * x + beta * y` for a symmetric packed `A`",
+      "trmv" => "computes `x = op(A) * x` for a triangular `A`",
+      "tbmv" => "computes `x = op(A) * x` for a triangular banded `A`",
+      "tpmv" => "computes `x = op(A) * x` for a triangular packed `A`",
+      "trsv" => "solves `op(A) * x = b` in place for a triangular `A`",
+      "tbsv" => "solves `op(A) * x = b` in place for a triangular banded `A`",
+      "tpsv" => "solves `op(A) * x = b` in place for a triangular packed `A`",
+      "ger" | "geru" => "computes the rank-1 update `A = alpha * x * y^T + A`",
+      "gerc" => "computes the rank-1 update `A = alpha * x * y^H + A`",
+      "her" => "computes the Hermitian rank-1 update `A = alpha * x * x^H + A`",
+      "hpr" => "computes the packed Hermitian rank-1 update `A = alpha * x * x^H + A`",
+      "her2" => {
+        "computes the Hermitian rank-2 update `A = alpha * x * y^H + conj(alpha) * y * x^H + A`"
+      }
+      "hpr2" => {
+        "computes the packed Hermitian rank-2 update `A = alpha * x * y^H + conj(alpha) * y * x^H + A`"
+      }
+      "syr" => "computes the symmetric rank-1 update `A = alpha * x * x^T + A`",
+      "spr" => "computes the packed symmetric rank-1 update `A = alpha * x * x^T + A`",
+      "syr2" => "computes the symmetric rank-2 update `A = alpha * (x * y^T + y * x^T) + A`",
+      "spr2" => "computes the packed symmetric rank-2 update `A = alpha * (x * y^T + y * x^T) + A`",
+      "gemm" => "computes `C = alpha * op(A) * op(B) + beta * C`",
+      "symm" => "computes `C = alpha * A * B + beta * C` (or `B * A`) for a symmetric `A`",
+      "hemm" => "computes `C = alpha * A * B + beta * C` (or `B * A`) for a Hermitian `A`",
+      "syrk" => "computes the rank-k update `C = alpha * op(A) * op(A)^T + beta * C`",
+      "herk" => "computes the rank-k update `C = alpha * op(A) * op(A)^H + beta * C`",
+      "syr2k" => {
+        "computes the rank-2k update `C = alpha * (op(A) * op(B)^T + op(B) * op(A)^T) + beta * C`"
+      }
+      "her2k" => {
+        "computes the rank-2k update `C = alpha * op(A) * op(B)^H + conj(alpha) * op(B) * op(A)^H + beta * C`"
+      }
+      "trmm" => "computes `B = alpha * op(A) * B` (or `B * op(A)`) for a triangular `A`",
+      "trsm" => "solves `op(A) * X = alpha * B` (or `X * op(A)`) in place for a triangular `A`",
+      "had" => "computes the element-wise product `z = alpha * x * y + beta * z`",
+      "omatcopy" => "copies a matrix, `B = alpha * op(A)`",
+      "im2col" => "unrolls image patches into columns for a convolution",
+      "col2im" => "accumulates columns back into an image, the inverse of `im2col`",
+      "convgemm" => "computes a batched 2D convolution as a `gemm`",
+      "axpybatched" => "runs a batch of `axpy`s with per-entry offsets and scalars",
+      "gemmbatched" => "runs a batch of `gemm`s with per-entry offsets and scalars",
+      "gemmstridedbatched" => "runs a batch of equally strided `gemm`s",
+      "gemmwithtempbuffer" => {
+        "computes `C = alpha * op(A) * op(B) + beta * C` in caller-provided scratch"
+      }
+      "gemmtempbuffersize" => {
+        "reports the scratch bytes the matching `gemm_with_temp_buffer` needs"
+      }
+      _ => return None,
+    };
+    Some(summary)
+  }
 
   fn is_ident(ty: &Type, want: &str) -> bool {
     if let Type::Path(tp) = ty {
@@ -353,12 +887,104 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
     }
     false
   }
+  /// `Offset` for `*_offset`/`offset_*` and `Ld` for `*_ld`/`ld*` scalar
+  /// (`usize`) parameters, so the two cannot be swapped by accident.
+  fn position_newtype(name: &Ident, ty: &Type) -> Option<Ident> {
+    if !is_ident(ty, "usize") {
+      return None;
+    }
+    let name = name.to_string();
+    if name.ends_with("_offset") || name.starts_with("offset_") {
+      Some(format_ident!("Offset"))
+    } else if name.ends_with("_ld") || name.starts_with("ld") {
+      Some(format_ident!("Ld"))
+    } else {
+      None
+    }
+  }
+  /// The `Sc*`/`Dz*` reductions of a complex vector into a real result
+  /// (`scopy`, `scol2im`, ... merely start with the same letters).
+  fn is_mixed_reduction(cname: &str) -> bool {
+    let name = cname.trim_start_matches("CLBlast");
+    matches!(name.get(2..), Some("nrm2" | "asum" | "sum"))
+      && (name.starts_with("Sc") || name.starts_with("Dz"))
+  }
+  /// `crate::precision` marker for the data buffers of `cname`, from its
+  /// precision prefix (the complex input for the mixed `Sc*`/`Dz*`).
+  fn element_precision(cname: &str) -> &'static str {
+    let name = cname.trim_start_matches("CLBlast");
+    if is_mixed_reduction(cname) {
+      return if name.starts_with("Sc") {
+        "ComplexSingle"
+      } else {
+        "ComplexDouble"
+      };
+    }
+    match name.strip_prefix('i').unwrap_or(name).chars().next() {
+      Some('S') => "Single",
+      Some('D') => "Double",
+      Some('C') => "ComplexSingle",
+      Some('Z') => "ComplexDouble",
+      Some('H') => "Half",
+      _ => panic!("no precision prefix on {cname}"),
+    }
+  }
 
   for item in file.items.iter() {
+    if let Item::Enum(ie) = item {
+      // Group every CLBlast enum in `consts`, documented with its values.
+      let name = ie.ident.to_string();
+      let alias = name.trim_end_matches('_');
+      if alias.starts_with("CLBlast") {
+        let alias_ident = format_ident!("{}", alias);
+        let values = ie
+          .variants
+          .iter()
+          .map(|v| match &v.discriminant {
+            Some((_, e)) => format!(
+              "`{}` (`{}`)",
+              v.ident,
+              quote!(#e).to_string().replace(' ', "")
+            ),
+            None => format!("`{}`", v.ident),
+          })
+          .collect::<Vec<_>>()
+          .join(", ");
+        let doc = if alias == "CLBlastStatusCode" {
+          format!(
+            " `{}` from `clblast_c.h`; see `all_status_codes` for every value.",
+            alias
+          )
+        } else {
+          format!(" `{}` from `clblast_c.h`: {}.", alias, values)
+        };
+        const_exports.push((
+          alias.to_string(),
+          quote! {
+            #[doc = #doc]
+            pub use crate::clblast_sys::#alias_ident;
+          },
+        ));
+      }
+    }
+
+    if let Item::Enum(ie) = item {
+      if ie.ident.to_string().trim_end_matches('_') == "CLBlastStatusCode" {
+        for v in ie.variants.iter() {
+          let variant = &v.ident;
+          let name = format!("CLBlast{}", variant);
+          status_codes.push(quote! { (sys::CLBlastStatusCode::#variant, #name) });
+        }
+      }
+    }
+
     if let Item::Const(ic) = item {
       if ic.ident.to_string().starts_with("CLBlast") {
         let ident = &ic.ident;
-        const_exports.push(quote! { pub use crate::clblast_sys::#ident; });
+        const_exports.push((
+          ident.to_string(),
+          quote! { pub use crate::clblast_sys::#ident; },
+        ));
       }
     }
 
@@ -382,6 +1008,10 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
 
           let wident = format_ident!("{}", cname.trim_start_matches("CLBlast").to_snake_case());
           let corename = format_ident!("{}", cname); // sys::CLBlastXxx
+          let gate = match routine_feature(&cname) {
+            Some(feature) => quote! { #[cfg(feature = #feature)] },
+            None => quote! {},
+          };
 
           let mut args: Vec<(Ident, Type)> = Vec::new();
           for a in f.sig.inputs.iter() {
@@ -407,11 +1037,30 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
           let returns_status =
             matches!(&f.sig.output, ReturnType::Type(_, ty) if is_ident(&*ty, "CLBlastStatusCode"));
 
+          // Raw pointers other than the queue/event pair (per-entry arrays of
+          // the batched routines, C strings, out-parameters) cannot be checked
+          // here, so their wrappers are `unsafe fn`s; the overlays that build
+          // them from slices are the safe entry points.
+          let raw_ptrs: Vec<String> = args
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, ty))| {
+              !(has_qe && (*i == qi || *i == ei)) && matches!(ty, Type::Ptr(_))
+            })
+            .map(|(_, (name, _))| format!("`{}`", name))
+            .collect();
+          let is_unsafe = !raw_ptrs.is_empty();
+          let unsafety = if is_unsafe {
+            quote! { unsafe }
+          } else {
+            quote! {}
+          };
+
           let mut wrapper_params: Vec<proc_macro2::TokenStream> = Vec::new();
           let mut call_args: Vec<proc_macro2::TokenStream> = Vec::new();
           let mut generics: Vec<proc_macro2::TokenStream> = Vec::new();
           let mut where_bounds: Vec<proc_macro2::TokenStream> = Vec::new();
-          let mut t_idx = 0usize;
+          let mut buffer_generics: Vec<String> = Vec::new();
 
           for (i, (name, ty)) in args.iter().enumerate() {
             if has_qe && (i == qi || i == ei) {
@@ -419,12 +1068,59 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
             }
 
             if is_ident(ty, "cl_mem") {
-              t_idx += 1;
-              let g = format_ident!("T{}", t_idx);
-              wrapper_params.push(quote! { #name: &ocl::Buffer<#g> });
+              // An `ocl::Buffer` or (`raw-api`) `RawMem`. The data buffers of
+              // one call share `T`, whose elements must match the routine's
+              // precision, so `sgemm` on `Buffer<f64>` does not compile; only
+              // the integer index of `i*amax`/`i*amin` (`I`), the real result
+              // of the mixed `Sc*`/`Dz*` reductions (`R`) and scratch memory
+              // (`S`, sized in bytes) differ.
+              let is_mixed = is_mixed_reduction(&cname);
+              let role = if name == "imax_buffer" || name == "imin_buffer" {
+                "I"
+              } else if name == "temp_buffer" {
+                "S"
+              } else if is_mixed && name != "x_buffer" {
+                "R"
+              } else {
+                "T"
+              };
+              let g = format_ident!("{}", role);
+              wrapper_params.push(quote! { #name: &#g });
               call_args.push(quote! { to_mem(#name) });
-              generics.push(quote! { #g });
-              where_bounds.push(quote! { #g: ocl::OclPrm });
+              if !buffer_generics.iter().any(|seen| seen == role) {
+                buffer_generics.push(role.to_string());
+                generics.push(quote! { #g });
+                let precision = match role {
+                  "S" => None,
+                  "I" => Some("Index"),
+                  "R" if cname.starts_with("CLBlastSc") => Some("Single"),
+                  "R" => Some("Double"),
+                  _ => Some(element_precision(&cname)),
+                };
+                where_bounds.push(match precision {
+                  Some(p) => {
+                    let p = format_ident!("{}", p);
+                    quote! { #g: AsMem<Elem: crate::Element<Precision = crate::precision::#p>> }
+                  }
+                  None => quote! { #g: AsMem },
+                });
+              }
+            } else if cname.starts_with("CLBlastH") && is_ident(ty, "cl_half") {
+              // `half::f16` with the `f16` feature, raw bits without.
+              wrapper_params.push(quote! { #name: crate::Half });
+              call_args.push(quote! { crate::float16::half_bits(#name) });
+            } else if is_ident(ty, "cl_float2") || is_ident(ty, "cl_double2") {
+              // `num_complex` scalars with the `complex` feature.
+              let (alias, conv) = if is_ident(ty, "cl_float2") {
+                (format_ident!("ComplexF32"), format_ident!("float2"))
+              } else {
+                (format_ident!("ComplexF64"), format_ident!("double2"))
+              };
+              wrapper_params.push(quote! { #name: crate::#alias });
+              call_args.push(quote! { crate::complex::#conv(#name) });
+            } else if let Some(newtype) = position_newtype(name, ty) {
+              wrapper_params.push(quote! { #name: crate::#newtype });
+              call_args.push(quote! { #name.0 });
             } else {
               wrapper_params.push(quote! { #name: #ty });
               call_args.push(quote! { #name });
@@ -432,15 +1128,16 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
           }
 
           if has_qe {
-            wrapper_params.insert(0, quote! { queue: &ocl::Queue });
-            wrapper_params.push(quote! { wait_for: &[CoreEvent] });
+            wrapper_params.insert(0, quote! { queue: &impl AsQueue });
+            wrapper_params.push(quote! { wait_for: impl IntoIterator<Item = &'a CoreEvent> });
+            generics.insert(0, quote! { 'a });
           }
 
           let wrapper_ret = if returns_status {
             if has_qe {
-              quote! { ocl::Result<Option<CoreEvent>> }
+              quote! { crate::Result<Option<CoreEvent>> }
             } else {
-              quote! { ocl::Result<()> }
+              quote! { crate::Result<()> }
             }
           } else {
             match &f.sig.output {
@@ -452,21 +1149,22 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
           let body = if returns_status {
             if has_qe {
               quote! {
+                let queue = &crate::queue::in_order_queue(queue)?;
                 let _marker = enqueue_marker_wait(queue, wait_for)?;
                 let mut raw_ev: sys::cl_event = std::ptr::null_mut();
                 let status = with_queue_ptr(queue, |qptr| unsafe {
                   sys::#corename(#(#call_args,)* qptr, &mut raw_ev as *mut _)
                 });
                 if !clblast_ok(status) {
-                  return Err(ocl::Error::from(format!(concat!(stringify!(#corename), " failed: code={:?}"), status)));
+                  return Err(clblast_status_to_err(status));
                 }
-                Ok(unsafe { wrap_new_event(raw_ev) })
+                unsafe { completion_event(queue, raw_ev) }
               }
             } else {
               quote! {
                 let status = unsafe { sys::#corename(#(#call_args,)*) };
                 if !clblast_ok(status) {
-                  return Err(ocl::Error::from(format!(concat!(stringify!(#corename), " failed: code={:?}"), status)));
+                  return Err(clblast_status_to_err(status));
                 }
                 Ok(())
               }
@@ -482,6 +1180,18 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
             }
           };
 
+          // Optional per-call span; compiled out unless the `tracing` feature is on.
+          let dims: Vec<&Ident> = args
+            .iter()
+            .map(|(name, _)| name)
+            .filter(|name| *name == "m" || *name == "n" || *name == "k")
+            .collect();
+          let span = quote! {
+            #[cfg(feature = "tracing")]
+            let _span = tracing::trace_span!("clblast", routine = stringify!(#corename) #(, #dims)*).entered();
+          };
+          let body = quote! { #span #body };
+
           let gdef = if generics.is_empty() {
             quote! {}
           } else {
@@ -493,88 +1203,285 @@ fn generate_ocl_wrappers(bindings_rs: &std::path::Path, out_wrappers: &std::path
             quote! { where #(#where_bounds,)* }
           };
 
-          fn_wrappers.push(quote! {
-            #[allow(clippy::too_many_arguments)]
-            pub fn #wident #gdef ( #(#wrapper_params,)* ) -> #wrapper_ret #gwhr { #body }
-          });
+          let kind = if is_unsafe { "Wrapper" } else { "Safe wrapper" };
+          let mut docs = vec![match routine_summary(&cname) {
+            Some(summary) => format!(" {} over `{}`: {}.", kind, cname, summary),
+            None => format!(" {} over `{}`.", kind, cname),
+          }];
+          if has_qe {
+            docs.push(String::new());
+            docs.push(format!(
+              " `queue` stands in for the C `{}` argument, and the routine starts",
+              args[qi].0
+            ));
+            docs.push(" once every event in `wait_for` has completed.".to_string());
+            if returns_status {
+              docs.push(format!(
+                " The C `{}` out-parameter becomes the returned event.",
+                args[ei].0
+              ));
+            }
+          }
+          let mut safety = Vec::new();
+          if is_unsafe {
+            safety.push(String::new());
+            safety.push(" # Safety".to_string());
+            safety.push(String::new());
+            let count = ["batch_count", "num_parameters"]
+              .into_iter()
+              .find(|count| args.iter().any(|(name, _)| name == count));
+            let extent = match count {
+              Some(count) => format!(" (`{}` entries each)", count),
+              None => String::new(),
+            };
+            safety.push(format!(
+              " {} must be valid for every read or write CLBlast makes through them{}.",
+              raw_ptrs.join(", "),
+              extent
+            ));
+          }
+          docs.extend(safety.iter().cloned());
+
+          fn_wrappers.push((
+            wident.to_string(),
+            quote! {
+              #(#[doc = #docs])*
+              #gate
+              #[allow(clippy::too_many_arguments)]
+              pub #unsafety fn #wident #gdef ( #(#wrapper_params,)* ) -> #wrapper_ret #gwhr { #body }
+            },
+          ));
           wrapped_count += 1;
+
+          // `raw-api`: same call, but the completion event is written into a
+          // caller-owned slot instead of being wrapped in a new `CoreEvent`.
+          if has_qe && returns_status {
+            let rident = format_ident!("{}_into_event", wident);
+            let mut raw_params = wrapper_params.clone();
+            raw_params.push(quote! { out_event: &mut std::mem::MaybeUninit<sys::cl_event> });
+            fn_wrappers.push((
+              rident.to_string(),
+              quote! {
+                /// Raw-event variant: the completion `cl_event` is written to `out_event`
+                /// (null if none was produced). The caller owns it and must release it
+                /// with `clReleaseEvent`.
+                #(#[doc = #safety])*
+                #gate
+                #[cfg(feature = "raw-api")]
+                #[allow(clippy::too_many_arguments)]
+                pub #unsafety fn #rident #gdef ( #(#raw_params,)* ) -> crate::Result<()> #gwhr {
+                  #span
+                  let queue = &crate::queue::in_order_queue(queue)?;
+                  let _marker = enqueue_marker_wait(queue, wait_for)?;
+                  out_event.write(std::ptr::null_mut());
+                  let status = with_queue_ptr(queue, |qptr| unsafe {
+                    sys::#corename(#(#call_args,)* qptr, out_event.as_mut_ptr())
+                  });
+                  if !clblast_ok(status) {
+                    return Err(clblast_status_to_err(status));
+                  }
+                  Ok(())
+                }
+              },
+            ));
+
+            // `blocking-wrappers`: the same call, then wait for its event.
+            let bident = format_ident!("{}_blocking", wident);
+            let forward: Vec<&Ident> = args
+              .iter()
+              .enumerate()
+              .filter(|(i, _)| *i != qi && *i != ei)
+              .map(|(_, (name, _))| name)
+              .collect();
+            let call_wrapped = if is_unsafe {
+              // Same contract as this function's own.
+              quote! { unsafe { #wident(queue, #(#forward,)* wait_for)? } }
+            } else {
+              quote! { #wident(queue, #(#forward,)* wait_for)? }
+            };
+            let bdoc = format!(
+              " Blocking [`{}`]: waits for the routine to complete.",
+              wident
+            );
+            fn_wrappers.push((
+              bident.to_string(),
+              quote! {
+                #[doc = #bdoc]
+                #(#[doc = #safety])*
+                #gate
+                #[cfg(feature = "blocking-wrappers")]
+                #[allow(clippy::too_many_arguments)]
+                pub #unsafety fn #bident #gdef ( #(#wrapper_params,)* ) -> crate::Result<()> #gwhr {
+                  if let Some(event) = #call_wrapped {
+                    event.wait_for().map_err(ocl::Error::from)?;
+                  }
+                  Ok(())
+                }
+              },
+            ));
+          }
         }
       }
     }
   }
 
+  const_exports.sort_by(|a, b| a.0.cmp(&b.0));
+  fn_wrappers.sort_by(|a, b| a.0.cmp(&b.0));
+  let const_exports = const_exports.into_iter().map(|(_, tokens)| tokens);
+  let fn_wrappers = fn_wrappers.into_iter().map(|(_, tokens)| tokens);
+
   let out = quote! {
     // ===== AUTO-GENERATED: CLBlast ocl wrappers =====
     // This file is auto-generated by clblast-binding.
 
     use crate::clblast_sys as sys;
     use ocl::core as ocore;
-    use ocl::{Buffer, Queue};
+    use crate::status::{clblast_ok, clblast_status_to_err, opencl_error_name};
     pub use ocore::Event as CoreEvent;
     use sys::*;
+
+    /// Handles cross between the bindgen types (`sys::cl_*`, from the OpenCL
+    /// headers CLBlast was built against) and `cl_sys::cl_*` (used by `ocl`)
+    /// with plain `as` casts. Both are opaque pointers to the same runtime
+    /// objects; this fails the build if their size or alignment ever differ.
+    const _: () = {
+      use std::mem::{align_of, size_of};
+      assert!(size_of::<sys::cl_event>() == size_of::<cl_sys::cl_event>());
+      assert!(align_of::<sys::cl_event>() == align_of::<cl_sys::cl_event>());
+      assert!(size_of::<sys::cl_mem>() == size_of::<cl_sys::cl_mem>());
+      assert!(align_of::<sys::cl_mem>() == align_of::<cl_sys::cl_mem>());
+      assert!(size_of::<sys::cl_command_queue>() == size_of::<cl_sys::cl_command_queue>());
+      assert!(align_of::<sys::cl_command_queue>() == align_of::<cl_sys::cl_command_queue>());
+      assert!(size_of::<sys::cl_device_id>() == size_of::<cl_sys::cl_device_id>());
+      assert!(align_of::<sys::cl_device_id>() == align_of::<cl_sys::cl_device_id>());
+    };
+
     #[inline]
-    pub fn with_queue_ptr<R>(queue: &Queue, f: impl FnOnce(*mut cl_command_queue) -> R) -> R {
-      let raw_cq_sys = queue.as_core().as_ptr();
+    pub fn with_queue_ptr<R>(queue: &impl AsQueue, f: impl FnOnce(*mut cl_command_queue) -> R) -> R {
+      let raw_cq_sys = queue.as_raw_queue();
 
       let mut cq_bindgen: cl_command_queue = raw_cq_sys as *mut _;
       let cq_ptr: *mut cl_command_queue = &mut cq_bindgen as *mut _;
       f(cq_ptr)
     }
+    /// The buffer's own `cl_mem`. For a sub-buffer this is the sub-buffer
+    /// handle, whose origin the OpenCL runtime applies itself, so every
+    /// CLBlast `*_offset` is relative to the start of `buf` (in elements)
+    /// and must not include the sub-buffer origin.
     #[inline]
-    fn to_mem<T: ocl::OclPrm>(buf: &Buffer<T>) -> sys::cl_mem {
-      buf.as_core().as_ptr() as sys::cl_mem
+    #[allow(dead_code)]
+    fn to_mem(buf: &impl AsMem) -> sys::cl_mem {
+      buf.as_raw_mem() as sys::cl_mem
     }
-    #[inline]
-    pub fn enqueue_marker_wait<'a>(
-      queue: &ocl::Queue,
-      wait_for: &[CoreEvent],
-    ) -> ocl::Result<Option<CoreEvent>> {
-      if wait_for.is_empty() {
-        return Ok(None);
-      }
+    /// Enqueue a marker on `queue` that completes once `raw_events` have,
+    /// or once every command enqueued so far has if the list is empty.
+    fn enqueue_marker(queue: &impl AsQueue, raw_events: &[cl_sys::cl_event]) -> ocl::Result<CoreEvent> {
       unsafe {
-        let cq = queue.as_core().as_ptr();
-        // Create a raw wait-list:
-        let mut raw_events: Vec<cl_sys::cl_event> = Vec::with_capacity(wait_for.len());
-        for e in wait_for {
-          // Safety: just borrowing the inner pointer (no retain here).
-          let ptr_ref = e.as_ptr_ref();
-          raw_events.push(*ptr_ref);
-        }
+        let cq = queue.as_raw_queue();
         let mut marker: cl_sys::cl_event = std::ptr::null_mut();
         let err = cl_sys::clEnqueueMarkerWithWaitList(
           cq,
           raw_events.len() as u32,
-          raw_events.as_ptr(),
+          if raw_events.is_empty() { std::ptr::null() } else { raw_events.as_ptr() },
           &mut marker as *mut _,
         );
         if err != cl_sys::CL_SUCCESS as i32 {
           return Err(ocl::Error::from(format!(
-            "clEnqueueMarkerWithWaitList failed: {}",
+            "clEnqueueMarkerWithWaitList failed: {} ({})",
+            opencl_error_name(err),
             err
           )));
         }
-        // Wrap marker event:
-        let ev = ocore::types::abs::Event::from_raw_create_ptr(marker);
-        Ok(Some(ev))
+        // clEnqueueMarkerWithWaitList hands back a new event with a
+        // reference count of one that belongs to us: adopt it, no retain.
+        Ok(ocore::types::abs::Event::from_raw_create_ptr(marker))
       }
     }
+    /// Wait lists up to this long are built without allocating.
+    const INLINE_WAIT_LIST: usize = 8;
+    /// Enqueue a marker waiting on `wait_for` so the routine enqueued next
+    /// orders after it. Wrappers hold the marker only for the duration of
+    /// the call and return the routine's completion event instead.
+    /// Does nothing under [`crate::sync::assume_in_order`].
     #[inline]
-    fn clblast_ok(code: sys::CLBlastStatusCode) -> bool {
-      (code as i32) == 0
+    pub fn enqueue_marker_wait<'a>(
+      queue: &impl AsQueue,
+      wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+    ) -> ocl::Result<Option<CoreEvent>> {
+      if crate::sync::assumes_in_order() {
+        return Ok(None);
+      }
+      // Create a raw wait-list; short lists stay on the stack and only
+      // longer ones spill to the heap.
+      // Safety: just borrowing the inner pointers (no retain here); the
+      // events outlive the call.
+      let mut inline: [cl_sys::cl_event; INLINE_WAIT_LIST] =
+        [std::ptr::null_mut(); INLINE_WAIT_LIST];
+      let mut spill: Vec<cl_sys::cl_event> = Vec::new();
+      let mut len = 0usize;
+      for e in wait_for {
+        let raw = unsafe { *e.as_ptr_ref() };
+        if len < INLINE_WAIT_LIST {
+          inline[len] = raw;
+        } else {
+          if spill.is_empty() {
+            spill.extend_from_slice(&inline);
+          }
+          spill.push(raw);
+        }
+        len += 1;
+      }
+      if len == 0 {
+        return Ok(None);
+      }
+      let raw_events = if len <= INLINE_WAIT_LIST {
+        &inline[..len]
+      } else {
+        &spill[..]
+      };
+      enqueue_marker(queue, raw_events).map(Some)
     }
+    /// Adopt an event the caller owns. CLBlast writes the event of its last
+    /// kernel to the `event` out-parameter and keeps no reference of its own
+    /// (intermediate events are released internally), so the one reference
+    /// moves into the `CoreEvent` and is released when it drops.
+    ///
+    /// Safety: `raw` must be null or an event nobody else will release.
     #[inline]
+    #[allow(dead_code)]
     unsafe fn wrap_new_event(raw: sys::cl_event) -> Option<CoreEvent> {
       if raw.is_null() {
         None
       } else {
         let raw_sys = raw as cl_sys::cl_event;
-        Some(ocore::types::abs::Event::from_raw_create_ptr(raw_sys))
+        Some(unsafe { ocore::types::abs::Event::from_raw_create_ptr(raw_sys) })
+      }
+    }
+    /// The completion event of a routine CLBlast just accepted: its own
+    /// event, or a marker enqueued behind it when CLBlast left `raw` null
+    /// (e.g. it had nothing to enqueue), so a successful submit always
+    /// yields `Some`.
+    ///
+    /// Safety: `raw` must be null or a new event owned by the caller.
+    #[allow(dead_code)]
+    unsafe fn completion_event(queue: &impl AsQueue, raw: sys::cl_event) -> crate::Result<Option<CoreEvent>> {
+      match unsafe { wrap_new_event(raw) } {
+        Some(event) => Ok(Some(event)),
+        None => Ok(Some(enqueue_marker(queue, &[])?)),
       }
     }
 
+    /// CLBlast enums and constants in one place. Each enum is listed with its
+    /// variants and their C values.
     pub mod consts { #(#const_exports)* }
 
+    /// Every `CLBlastStatusCode` with its upstream name (e.g.
+    /// `"CLBlastInvalidBufferSize"`), in declaration order.
+    pub fn all_status_codes() -> &'static [(sys::CLBlastStatusCode, &'static str)] {
+      const CODES: &[(sys::CLBlastStatusCode, &str)] = &[#(#status_codes),*];
+      CODES
+    }
+
     #(#fn_wrappers)*
   };
 
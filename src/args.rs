@@ -0,0 +1,36 @@
+//! Newtypes for the position arguments of the generated wrappers.
+//!
+//! Offsets and leading dimensions are both counted in elements and sit side
+//! by side in most signatures, so as bare `usize`s they are easy to swap.
+//! The generated wrappers take [`Offset`] for every `*_offset` and [`Ld`]
+//! for every `*_ld`; the validated overlays keep plain `usize`.
+//!
+//! Passing one where the other is expected no longer compiles:
+//!
+//! ```compile_fail
+//! fn scale(queue: &ocl::Queue, x: &ocl::Buffer<f32>) -> clblast_binding::Result<()> {
+//!   use clblast_binding::Ld;
+//!   clblast_binding::sscal(queue, 4, 2.0, x, Ld(0), 1, &[])?;
+//!   Ok(())
+//! }
+//! ```
+
+/// Offset of a vector or matrix in its buffer, in elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Offset(pub usize);
+
+/// Leading dimension (row or column pitch) of a matrix, in elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Ld(pub usize);
+
+impl From<usize> for Offset {
+  fn from(offset: usize) -> Offset {
+    Offset(offset)
+  }
+}
+
+impl From<usize> for Ld {
+  fn from(ld: usize) -> Ld {
+    Ld(ld)
+  }
+}
@@ -0,0 +1,83 @@
+//! Reusable `sgemm` throughput measurement.
+
+use ocl::core::{ProfilingInfo, get_event_profiling_info};
+use ocl::{Buffer, Queue};
+
+use crate::CoreEvent;
+use crate::builder::sgemm_auto;
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+use crate::error::CLBlastError;
+
+/// `CL_PROFILING_COMMAND_END` of `event`, in nanoseconds, after waiting on it.
+fn end_time(event: &CoreEvent) -> Result<u64, CLBlastError> {
+  event.wait_for().map_err(ocl::Error::from)?;
+  Ok(
+    get_event_profiling_info(event, ProfilingInfo::End)
+      .map_err(ocl::Error::from)?
+      .time()
+      .map_err(ocl::Error::from)?,
+  )
+}
+
+/// Throughput of a row-major, non-transposed `m x n x k` [`sgemm_auto`] in
+/// GFLOP/s, i.e. `2 * m * n * k * iters / seconds / 1e9`.
+///
+/// Allocates the three operands on `queue`, runs one untimed warm-up call
+/// (which also triggers CLBlast's kernel compilation), then enqueues `iters`
+/// calls back to back. The time is taken from the warm-up's end to the last
+/// call's end, so it covers every kernel CLBlast launches per call, not only
+/// the one whose event it returns. Blocks until done.
+///
+/// `queue` must be in-order and created with
+/// `CommandQueueProperties::PROFILING_ENABLE`. Zero sizes or `iters == 0`
+/// are rejected with `InvalidDimension`.
+pub fn bench_sgemm(
+  queue: &Queue,
+  m: usize,
+  n: usize,
+  k: usize,
+  iters: usize,
+) -> Result<f64, CLBlastError> {
+  if m == 0 || n == 0 || k == 0 || iters == 0 {
+    return Err(CLBlastError::InvalidDimension);
+  }
+  let filled = |len: usize, value: f32| {
+    Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(len)
+      .fill_val(value)
+      .build()
+  };
+  let a = filled(m * k, 1.0)?;
+  let b = filled(k * n, 1.0)?;
+  let c = filled(m * n, 0.0)?;
+  let gemm = || {
+    sgemm_auto(
+      queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      m,
+      n,
+      k,
+      1.0,
+      &a,
+      &b,
+      0.0,
+      &c,
+      &[],
+    )?
+    .ok_or(CLBlastError::InvalidDimension)
+  };
+
+  let start = end_time(&gemm()?)?;
+  let mut last = None;
+  for _ in 0..iters {
+    last = Some(gemm()?);
+  }
+  let end = end_time(last.as_ref().expect("iters > 0"))?;
+
+  let seconds = end.saturating_sub(start) as f64 * 1e-9;
+  let flops = 2.0 * m as f64 * n as f64 * k as f64 * iters as f64;
+  Ok(flops / seconds / 1e9)
+}
@@ -13,7 +13,7 @@ pub union cl_float2 {
     pub __bindgen_anon_3: cl_float2__bindgen_ty_3,
 }
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub struct cl_float2__bindgen_ty_1 {
     pub x: cl_float,
     pub y: cl_float,
@@ -29,7 +29,7 @@ const _: () = {
         [::std::mem::offset_of!(cl_float2__bindgen_ty_1, y) - 4usize];
 };
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub struct cl_float2__bindgen_ty_2 {
     pub s0: cl_float,
     pub s1: cl_float,
@@ -45,7 +45,7 @@ const _: () = {
         [::std::mem::offset_of!(cl_float2__bindgen_ty_2, s1) - 4usize];
 };
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub struct cl_float2__bindgen_ty_3 {
     pub lo: cl_float,
     pub hi: cl_float,
@@ -76,7 +76,7 @@ pub union cl_double2 {
     pub __bindgen_anon_3: cl_double2__bindgen_ty_3,
 }
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub struct cl_double2__bindgen_ty_1 {
     pub x: cl_double,
     pub y: cl_double,
@@ -93,7 +93,7 @@ const _: () = {
         [::std::mem::offset_of!(cl_double2__bindgen_ty_1, y) - 8usize];
 };
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub struct cl_double2__bindgen_ty_2 {
     pub s0: cl_double,
     pub s1: cl_double,
@@ -110,7 +110,7 @@ const _: () = {
         [::std::mem::offset_of!(cl_double2__bindgen_ty_2, s1) - 8usize];
 };
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub struct cl_double2__bindgen_ty_3 {
     pub lo: cl_double,
     pub hi: cl_double,
@@ -133,31 +133,32 @@ const _: () = {
     ["Offset of field: cl_double2::s"][::std::mem::offset_of!(cl_double2, s) - 0usize];
 };
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub struct _cl_device_id {
     _unused: [u8; 0],
 }
 pub type cl_device_id = *mut _cl_device_id;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub struct _cl_command_queue {
     _unused: [u8; 0],
 }
 pub type cl_command_queue = *mut _cl_command_queue;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub struct _cl_mem {
     _unused: [u8; 0],
 }
 pub type cl_mem = *mut _cl_mem;
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub struct _cl_event {
     _unused: [u8; 0],
 }
 pub type cl_event = *mut _cl_event;
 #[repr(i32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastStatusCode_ {
     Success = 0,
     OpenCLCompilerNotAvailable = -3,
@@ -220,14 +221,16 @@ pub enum CLBlastStatusCode_ {
 }
 pub use self::CLBlastStatusCode_ as CLBlastStatusCode;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastLayout_ {
     RowMajor = 101,
     ColMajor = 102,
 }
 pub use self::CLBlastLayout_ as CLBlastLayout;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastTranspose_ {
     No = 111,
     Yes = 112,
@@ -235,35 +238,40 @@ pub enum CLBlastTranspose_ {
 }
 pub use self::CLBlastTranspose_ as CLBlastTranspose;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastTriangle_ {
     Upper = 121,
     Lower = 122,
 }
 pub use self::CLBlastTriangle_ as CLBlastTriangle;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastDiagonal_ {
     NonUnit = 131,
     Unit = 132,
 }
 pub use self::CLBlastDiagonal_ as CLBlastDiagonal;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastSide_ {
     Left = 141,
     Right = 142,
 }
 pub use self::CLBlastSide_ as CLBlastSide;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastKernelMode_ {
     CrossCorrelation = 151,
     Convolution = 152,
 }
 pub use self::CLBlastKernelMode_ as CLBlastKernelMode;
 #[repr(u32)]
-#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord, PartialEq, Eq)]
 pub enum CLBlastPrecision_ {
     Half = 16,
     Single = 32,
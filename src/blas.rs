@@ -0,0 +1,229 @@
+//! Queue-bound front end over the wrappers.
+
+use ocl::{Buffer, ProQue, Queue};
+
+use crate::CoreEvent;
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+use crate::error::CLBlastError;
+use crate::queue::AsQueue;
+use crate::{level1, level3};
+
+/// A command queue plus method forms of the most common routines.
+///
+/// Every method enqueues on the owned queue; arguments otherwise match the
+/// free function of the same name. `Blas` also implements [`AsQueue`], so it
+/// can be passed to any free function directly.
+///
+/// A default [`CLBlastLayout`] (row-major unless set with
+/// [`Blas::with_layout`]) is used by the `*_default_layout` methods; the
+/// methods taking an explicit `layout` ignore it.
+#[derive(Debug, Clone)]
+pub struct Blas {
+  queue: Queue,
+  layout: CLBlastLayout,
+}
+
+impl Blas {
+  /// Wrap `queue` (a cheap, reference-counted clone is fine) with a
+  /// row-major default layout.
+  pub fn new(queue: Queue) -> Blas {
+    Blas::with_layout(queue, CLBlastLayout::RowMajor)
+  }
+
+  /// Wrap `queue` with `layout` as the default layout.
+  pub fn with_layout(queue: Queue, layout: CLBlastLayout) -> Blas {
+    Blas { queue, layout }
+  }
+
+  /// The queue all calls are enqueued on.
+  pub fn queue(&self) -> &Queue {
+    &self.queue
+  }
+
+  /// The layout used by the `*_default_layout` methods.
+  pub fn layout(&self) -> CLBlastLayout {
+    self.layout
+  }
+
+  /// See [`level3::sgemm`]. `layout` overrides the default layout.
+  #[allow(clippy::too_many_arguments)]
+  pub fn sgemm<'a>(
+    &self,
+    layout: CLBlastLayout,
+    a_transpose: CLBlastTranspose,
+    b_transpose: CLBlastTranspose,
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: f32,
+    a_buffer: &Buffer<f32>,
+    a_offset: usize,
+    a_ld: usize,
+    b_buffer: &Buffer<f32>,
+    b_offset: usize,
+    b_ld: usize,
+    beta: f32,
+    c_buffer: &Buffer<f32>,
+    c_offset: usize,
+    c_ld: usize,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError> {
+    level3::sgemm(
+      &self.queue,
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      alpha,
+      a_buffer,
+      a_offset,
+      a_ld,
+      b_buffer,
+      b_offset,
+      b_ld,
+      beta,
+      c_buffer,
+      c_offset,
+      c_ld,
+      wait_for,
+    )
+  }
+
+  /// [`Blas::sgemm`] using the default layout.
+  #[allow(clippy::too_many_arguments)]
+  pub fn sgemm_default_layout<'a>(
+    &self,
+    a_transpose: CLBlastTranspose,
+    b_transpose: CLBlastTranspose,
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: f32,
+    a_buffer: &Buffer<f32>,
+    a_offset: usize,
+    a_ld: usize,
+    b_buffer: &Buffer<f32>,
+    b_offset: usize,
+    b_ld: usize,
+    beta: f32,
+    c_buffer: &Buffer<f32>,
+    c_offset: usize,
+    c_ld: usize,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError> {
+    self.sgemm(
+      self.layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      alpha,
+      a_buffer,
+      a_offset,
+      a_ld,
+      b_buffer,
+      b_offset,
+      b_ld,
+      beta,
+      c_buffer,
+      c_offset,
+      c_ld,
+      wait_for,
+    )
+  }
+
+  /// See [`level1::saxpy`].
+  #[allow(clippy::too_many_arguments)]
+  pub fn saxpy<'a>(
+    &self,
+    n: usize,
+    alpha: f32,
+    x_buffer: &Buffer<f32>,
+    x_offset: usize,
+    x_inc: usize,
+    y_buffer: &Buffer<f32>,
+    y_offset: usize,
+    y_inc: usize,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError> {
+    level1::saxpy(
+      &self.queue,
+      n,
+      alpha,
+      x_buffer,
+      x_offset,
+      x_inc,
+      y_buffer,
+      y_offset,
+      y_inc,
+      wait_for,
+    )
+  }
+
+  /// See [`level1::sscal`].
+  pub fn sscal<'a>(
+    &self,
+    n: usize,
+    alpha: f32,
+    x_buffer: &Buffer<f32>,
+    x_offset: usize,
+    x_inc: usize,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError> {
+    level1::sscal(&self.queue, n, alpha, x_buffer, x_offset, x_inc, wait_for)
+  }
+
+  /// See [`level1::scopy`].
+  #[allow(clippy::too_many_arguments)]
+  pub fn scopy<'a>(
+    &self,
+    n: usize,
+    x_buffer: &Buffer<f32>,
+    x_offset: usize,
+    x_inc: usize,
+    y_buffer: &Buffer<f32>,
+    y_offset: usize,
+    y_inc: usize,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError> {
+    level1::scopy(
+      &self.queue,
+      n,
+      x_buffer,
+      x_offset,
+      x_inc,
+      y_buffer,
+      y_offset,
+      y_inc,
+      wait_for,
+    )
+  }
+}
+
+impl From<Queue> for Blas {
+  fn from(queue: Queue) -> Blas {
+    Blas::new(queue)
+  }
+}
+
+impl From<&Queue> for Blas {
+  fn from(queue: &Queue) -> Blas {
+    Blas::new(queue.clone())
+  }
+}
+
+impl From<&ProQue> for Blas {
+  fn from(pro_que: &ProQue) -> Blas {
+    Blas::new(pro_que.queue().clone())
+  }
+}
+
+// Safety: `Blas` owns a retained `ocl::Queue`.
+unsafe impl AsQueue for Blas {
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue {
+    self.queue.as_raw_queue()
+  }
+}
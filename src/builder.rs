@@ -0,0 +1,453 @@
+//! Convenience front ends that fill in arguments derivable from the shape,
+//! small `Default`-constructible configs bundling the enum knobs of
+//! routines that take several of them, and [`GemmBuilder`] for naming the
+//! arguments of a `gemm` instead of lining up eighteen positional ones.
+
+use ocl::Buffer;
+
+use crate::CoreEvent;
+use crate::clblast_sys::{
+  CLBlastDiagonal, CLBlastLayout, CLBlastSide, CLBlastTranspose, CLBlastTriangle,
+};
+use crate::error::CLBlastError;
+use crate::generic::{ClblastFloat, ClblastScalar};
+use crate::level3::{self, GemmShape};
+use crate::queue::AsQueue;
+
+/// Leading dimension of a densely packed `rows x cols` matrix (as stored,
+/// i.e. before applying `trans`) in the given layout.
+fn packed_ld(layout: CLBlastLayout, trans: CLBlastTranspose, rows: usize, cols: usize) -> usize {
+  let (stored_rows, stored_cols) = match trans {
+    CLBlastTranspose::No => (rows, cols),
+    CLBlastTranspose::Yes | CLBlastTranspose::Conjugate => (cols, rows),
+  };
+  let ld = match layout {
+    CLBlastLayout::RowMajor => stored_cols,
+    CLBlastLayout::ColMajor => stored_rows,
+  };
+  // BLAS requires `ld >= 1` even for empty matrices.
+  ld.max(1)
+}
+
+/// `sgemm` on densely packed matrices, with `lda`/`ldb`/`ldc` derived from
+/// the shape and all offsets set to `0`.
+///
+/// `op(A)` is `m x k`, `op(B)` is `k x n` and `C` is `m x n`. The leading
+/// dimensions used are:
+///
+/// | layout   | `lda` (No / Yes) | `ldb` (No / Yes) | `ldc` |
+/// |----------|------------------|------------------|-------|
+/// | RowMajor | `k` / `m`        | `n` / `k`        | `n`   |
+/// | ColMajor | `m` / `k`        | `k` / `n`        | `m`   |
+///
+/// `Conjugate` is treated like `Yes`. Zero-sized dimensions are handled as
+/// in [`level3::sgemm`]. Use [`level3::sgemm`] directly when a buffer is
+/// padded (row/column pitch larger than the logical size) or when a matrix
+/// starts at a nonzero offset.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm_auto<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  b_buffer: &Buffer<f32>,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  let a_ld = packed_ld(layout, a_transpose, m, k);
+  let b_ld = packed_ld(layout, b_transpose, k, n);
+  let c_ld = packed_ld(layout, CLBlastTranspose::No, m, n);
+  level3::sgemm(
+    queue,
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    0,
+    a_ld,
+    b_buffer,
+    0,
+    b_ld,
+    beta,
+    c_buffer,
+    0,
+    c_ld,
+    wait_for,
+  )
+}
+
+/// Named-argument front end for `gemm`:
+/// `C = alpha * op(A) * op(B) + beta * C`.
+///
+/// `m`, `n`, `k` and the three buffers are required. Everything else has a
+/// default: `RowMajor`, no transposes, `alpha = 1`, `beta = 0`, offsets
+/// `0`, and leading dimensions of densely packed matrices (see
+/// [`sgemm_auto`]).
+///
+/// ```ignore
+/// let event = GemmBuilder::new()
+///   .dims(m, n, k)
+///   .a(&a)
+///   .b(&b)
+///   .transpose_b(Transpose::Yes)
+///   .c(&c)
+///   .beta(1.0)
+///   .run(&queue, &[])?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct GemmBuilder<'a, T: ClblastFloat> {
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: Option<usize>,
+  n: Option<usize>,
+  k: Option<usize>,
+  alpha: T::Scalar,
+  beta: T::Scalar,
+  a: Option<&'a Buffer<T>>,
+  a_offset: usize,
+  a_ld: Option<usize>,
+  b: Option<&'a Buffer<T>>,
+  b_offset: usize,
+  b_ld: Option<usize>,
+  c: Option<&'a Buffer<T>>,
+  c_offset: usize,
+  c_ld: Option<usize>,
+}
+
+impl<'a, T: ClblastFloat> Default for GemmBuilder<'a, T> {
+  fn default() -> GemmBuilder<'a, T> {
+    GemmBuilder {
+      layout: CLBlastLayout::RowMajor,
+      a_transpose: CLBlastTranspose::No,
+      b_transpose: CLBlastTranspose::No,
+      m: None,
+      n: None,
+      k: None,
+      alpha: T::Scalar::ONE,
+      beta: T::Scalar::ZERO,
+      a: None,
+      a_offset: 0,
+      a_ld: None,
+      b: None,
+      b_offset: 0,
+      b_ld: None,
+      c: None,
+      c_offset: 0,
+      c_ld: None,
+    }
+  }
+}
+
+impl<'a, T: ClblastFloat> GemmBuilder<'a, T> {
+  /// A builder with every optional argument at its default.
+  pub fn new() -> GemmBuilder<'a, T> {
+    GemmBuilder::default()
+  }
+
+  /// Storage order of all three matrices.
+  pub fn layout(mut self, layout: CLBlastLayout) -> Self {
+    self.layout = layout;
+    self
+  }
+
+  /// `op(A)`.
+  pub fn transpose_a(mut self, trans: CLBlastTranspose) -> Self {
+    self.a_transpose = trans;
+    self
+  }
+
+  /// `op(B)`.
+  pub fn transpose_b(mut self, trans: CLBlastTranspose) -> Self {
+    self.b_transpose = trans;
+    self
+  }
+
+  /// Rows of `op(A)` and `C`.
+  pub fn m(mut self, m: usize) -> Self {
+    self.m = Some(m);
+    self
+  }
+
+  /// Columns of `op(B)` and `C`.
+  pub fn n(mut self, n: usize) -> Self {
+    self.n = Some(n);
+    self
+  }
+
+  /// Inner dimension.
+  pub fn k(mut self, k: usize) -> Self {
+    self.k = Some(k);
+    self
+  }
+
+  /// `m`, `n` and `k` at once.
+  pub fn dims(self, m: usize, n: usize, k: usize) -> Self {
+    self.m(m).n(n).k(k)
+  }
+
+  /// Scale of the product.
+  pub fn alpha(mut self, alpha: T::Scalar) -> Self {
+    self.alpha = alpha;
+    self
+  }
+
+  /// Scale of the existing contents of `C`.
+  pub fn beta(mut self, beta: T::Scalar) -> Self {
+    self.beta = beta;
+    self
+  }
+
+  /// Buffer holding `A`.
+  pub fn a(mut self, buffer: &'a Buffer<T>) -> Self {
+    self.a = Some(buffer);
+    self
+  }
+
+  /// Offset of `A` in its buffer, in elements.
+  pub fn a_offset(mut self, offset: usize) -> Self {
+    self.a_offset = offset;
+    self
+  }
+
+  /// Leading dimension of `A` as stored.
+  pub fn lda(mut self, ld: usize) -> Self {
+    self.a_ld = Some(ld);
+    self
+  }
+
+  /// Buffer holding `B`.
+  pub fn b(mut self, buffer: &'a Buffer<T>) -> Self {
+    self.b = Some(buffer);
+    self
+  }
+
+  /// Offset of `B` in its buffer, in elements.
+  pub fn b_offset(mut self, offset: usize) -> Self {
+    self.b_offset = offset;
+    self
+  }
+
+  /// Leading dimension of `B` as stored.
+  pub fn ldb(mut self, ld: usize) -> Self {
+    self.b_ld = Some(ld);
+    self
+  }
+
+  /// Buffer holding `C`, read (unless `beta == 0`) and written.
+  pub fn c(mut self, buffer: &'a Buffer<T>) -> Self {
+    self.c = Some(buffer);
+    self
+  }
+
+  /// Offset of `C` in its buffer, in elements.
+  pub fn c_offset(mut self, offset: usize) -> Self {
+    self.c_offset = offset;
+    self
+  }
+
+  /// Leading dimension of `C`.
+  pub fn ldc(mut self, ld: usize) -> Self {
+    self.c_ld = Some(ld);
+    self
+  }
+
+  /// The shape this builder describes, with defaulted leading dimensions
+  /// filled in.
+  ///
+  /// Fails with `InvalidDimension` if `m`, `n` or `k` is unset or a given
+  /// leading dimension is too small for the matrix it describes (e.g.
+  /// `lda < k` for a row-major, non-transposed `A`).
+  pub fn shape(&self) -> Result<GemmShape, CLBlastError> {
+    let (Some(m), Some(n), Some(k)) = (self.m, self.n, self.k) else {
+      return Err(CLBlastError::InvalidDimension);
+    };
+    let ld = |given: Option<usize>, trans, rows, cols| {
+      let packed = packed_ld(self.layout, trans, rows, cols);
+      match given {
+        None => Ok(packed),
+        Some(ld) if ld >= packed => Ok(ld),
+        Some(_) => Err(CLBlastError::InvalidDimension),
+      }
+    };
+    Ok(GemmShape {
+      layout: self.layout,
+      a_transpose: self.a_transpose,
+      b_transpose: self.b_transpose,
+      m,
+      n,
+      k,
+      a_ld: ld(self.a_ld, self.a_transpose, m, k)?,
+      b_ld: ld(self.b_ld, self.b_transpose, k, n)?,
+      c_ld: ld(self.c_ld, CLBlastTranspose::No, m, n)?,
+    })
+  }
+
+  /// Enqueue the `gemm` on `queue` through the validated `level3` overlay
+  /// of `T`'s precision.
+  ///
+  /// A missing buffer fails with `InvalidMatrixA`/`B`/`C`, a missing or
+  /// inconsistent dimension as in [`shape`](Self::shape); buffer footprints
+  /// are then checked by the overlay.
+  pub fn run<'e>(
+    &self,
+    queue: &impl AsQueue,
+    wait_for: impl IntoIterator<Item = &'e CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError> {
+    let a = self.a.ok_or(CLBlastError::InvalidMatrixA)?;
+    let b = self.b.ok_or(CLBlastError::InvalidMatrixB)?;
+    let c = self.c.ok_or(CLBlastError::InvalidMatrixC)?;
+    T::gemm_validated(
+      queue,
+      self.shape()?,
+      self.alpha,
+      a,
+      self.a_offset,
+      b,
+      self.b_offset,
+      self.beta,
+      c,
+      self.c_offset,
+      wait_for,
+    )
+  }
+}
+
+/// Enum arguments of `trsm`.
+///
+/// The default is `Side::Left`, `Triangle::Upper`, `Transpose::No`,
+/// `Diagonal::NonUnit`: solve `A * X = alpha * B` with a general upper
+/// triangular `A`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrsmConfig {
+  /// Which side `A` is applied from.
+  pub side: CLBlastSide,
+  /// Which triangle of `A` is read.
+  pub uplo: CLBlastTriangle,
+  /// `op(A)`.
+  pub trans: CLBlastTranspose,
+  /// Whether the diagonal of `A` is assumed to be all ones.
+  pub diag: CLBlastDiagonal,
+}
+
+impl Default for TrsmConfig {
+  fn default() -> TrsmConfig {
+    TrsmConfig {
+      side: CLBlastSide::Left,
+      uplo: CLBlastTriangle::Upper,
+      trans: CLBlastTranspose::No,
+      diag: CLBlastDiagonal::NonUnit,
+    }
+  }
+}
+
+/// Enum arguments of `symm`/`hemm`.
+///
+/// The default is `Side::Left`, `Triangle::Upper`: `C = alpha * A * B + beta * C`
+/// reading the upper triangle of `A`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SymmConfig {
+  /// Which side `A` is applied from.
+  pub side: CLBlastSide,
+  /// Which triangle of `A` is read.
+  pub uplo: CLBlastTriangle,
+}
+
+impl Default for SymmConfig {
+  fn default() -> SymmConfig {
+    SymmConfig {
+      side: CLBlastSide::Left,
+      uplo: CLBlastTriangle::Upper,
+    }
+  }
+}
+
+/// [`level3::strsm`] taking its enum arguments from `config`.
+#[allow(clippy::too_many_arguments)]
+pub fn strsm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  config: TrsmConfig,
+  m: usize,
+  n: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  level3::strsm(
+    queue,
+    layout,
+    config.side,
+    config.uplo,
+    config.trans,
+    config.diag,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    wait_for,
+  )
+}
+
+/// [`level3::ssymm`] taking its enum arguments from `config`.
+#[allow(clippy::too_many_arguments)]
+pub fn ssymm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  config: SymmConfig,
+  m: usize,
+  n: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  level3::ssymm(
+    queue,
+    layout,
+    config.side,
+    config.uplo,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    beta,
+    c_buffer,
+    c_offset,
+    c_ld,
+    wait_for,
+  )
+}
@@ -0,0 +1,45 @@
+//! Device capability queries.
+
+use ocl::Device;
+use ocl::enums::DeviceInfo;
+
+use crate::clblast_sys::CLBlastPrecision;
+use crate::error::CLBlastError;
+
+/// Precisions CLBlast can run on `device`, judged from its extension string.
+///
+/// `Single` and `ComplexSingle` are always reported; `Double` and
+/// `ComplexDouble` need `cl_khr_fp64` (or the older `cl_amd_fp64`), `Half`
+/// needs `cl_khr_fp16`.
+pub fn supported_precisions(device: &Device) -> Result<Vec<CLBlastPrecision>, CLBlastError> {
+  let extensions = device.info(DeviceInfo::Extensions)?.to_string();
+  let has = |ext: &str| extensions.split_whitespace().any(|e| e == ext);
+  let fp64 = has("cl_khr_fp64") || has("cl_amd_fp64");
+
+  let mut out = Vec::with_capacity(5);
+  if has("cl_khr_fp16") {
+    out.push(CLBlastPrecision::Half);
+  }
+  out.push(CLBlastPrecision::Single);
+  if fp64 {
+    out.push(CLBlastPrecision::Double);
+  }
+  out.push(CLBlastPrecision::ComplexSingle);
+  if fp64 {
+    out.push(CLBlastPrecision::ComplexDouble);
+  }
+  Ok(out)
+}
+
+/// `Ok(())` if `device` supports `precision`, otherwise `NoHalfPrecision` or
+/// `NoDoublePrecision`. Call before dispatching `h*`/`d*`/`z*` routines on a
+/// device of unknown capability.
+pub fn require_precision(device: &Device, precision: CLBlastPrecision) -> Result<(), CLBlastError> {
+  if supported_precisions(device)?.contains(&precision) {
+    return Ok(());
+  }
+  Err(match precision {
+    CLBlastPrecision::Half => CLBlastError::NoHalfPrecision,
+    _ => CLBlastError::NoDoublePrecision,
+  })
+}
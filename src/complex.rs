@@ -0,0 +1,76 @@
+//! Complex scalars of the `c*`/`z*` routines.
+//!
+//! CLBlast takes complex `alpha`/`beta` as `cl_float2`/`cl_double2`. With
+//! the `complex` feature the generated wrappers and the overlays take
+//! `num_complex::Complex<f32>`/`Complex<f64>` instead, converted to the
+//! two-element C union at the call boundary. Buffers of
+//! `num_complex::Complex<_>` work either way, as ocl implements `OclPrm`
+//! for them.
+
+use crate::clblast_sys::{cl_double2, cl_float2};
+
+/// Single-complex scalar: `num_complex::Complex<f32>` with the `complex`
+/// feature, `cl_float2` without.
+#[cfg(feature = "complex")]
+pub type ComplexF32 = num_complex::Complex<f32>;
+/// Single-complex scalar: `num_complex::Complex<f32>` with the `complex`
+/// feature, `cl_float2` without.
+#[cfg(not(feature = "complex"))]
+pub type ComplexF32 = cl_float2;
+
+/// Double-complex scalar: `num_complex::Complex<f64>` with the `complex`
+/// feature, `cl_double2` without.
+#[cfg(feature = "complex")]
+pub type ComplexF64 = num_complex::Complex<f64>;
+/// Double-complex scalar: `num_complex::Complex<f64>` with the `complex`
+/// feature, `cl_double2` without.
+#[cfg(not(feature = "complex"))]
+pub type ComplexF64 = cl_double2;
+
+/// `re + im * i` as a [`ComplexF32`].
+pub const fn complex_f32(re: f32, im: f32) -> ComplexF32 {
+  #[cfg(feature = "complex")]
+  return num_complex::Complex::new(re, im);
+  #[cfg(not(feature = "complex"))]
+  return cl_float2 { s: [re, im] };
+}
+
+/// `re + im * i` as a [`ComplexF64`].
+pub const fn complex_f64(re: f64, im: f64) -> ComplexF64 {
+  #[cfg(feature = "complex")]
+  return num_complex::Complex::new(re, im);
+  #[cfg(not(feature = "complex"))]
+  return cl_double2 { s: [re, im] };
+}
+
+/// `x` as CLBlast takes it.
+#[inline]
+pub(crate) fn float2(x: ComplexF32) -> cl_float2 {
+  #[cfg(feature = "complex")]
+  return cl_float2 { s: [x.re, x.im] };
+  #[cfg(not(feature = "complex"))]
+  return x;
+}
+
+/// `x` as CLBlast takes it.
+#[inline]
+pub(crate) fn double2(x: ComplexF64) -> cl_double2 {
+  #[cfg(feature = "complex")]
+  return cl_double2 { s: [x.re, x.im] };
+  #[cfg(not(feature = "complex"))]
+  return x;
+}
+
+/// `[re, im]` of `x`.
+#[inline]
+pub(crate) fn parts_f32(x: ComplexF32) -> [f32; 2] {
+  // Safety: every variant of the union is the same two floats.
+  unsafe { float2(x).s }
+}
+
+/// `[re, im]` of `x`.
+#[inline]
+pub(crate) fn parts_f64(x: ComplexF64) -> [f64; 2] {
+  // Safety: every variant of the union is the same two doubles.
+  unsafe { double2(x).s }
+}
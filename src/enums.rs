@@ -0,0 +1,101 @@
+//! Short names for the CLBlast enums, plus the transformations generic
+//! triangular code keeps open-coding.
+//!
+//! The aliases are the bindgen types themselves, so they can be passed to
+//! any wrapper unchanged. Bindgen derives `Debug, Clone, Copy, PartialEq,
+//! Eq, Hash, PartialOrd, Ord` on every rustified enum (ordered by C value),
+//! plus serde's `Serialize`/`Deserialize` with the `serde` feature; the
+//! check below keeps it that way.
+
+use crate::clblast_sys::{
+  CLBlastDiagonal, CLBlastKernelMode, CLBlastLayout, CLBlastPrecision, CLBlastSide,
+  CLBlastTranspose, CLBlastTriangle,
+};
+
+/// [`CLBlastLayout`].
+pub type Layout = CLBlastLayout;
+/// [`CLBlastTranspose`].
+pub type Transpose = CLBlastTranspose;
+/// [`CLBlastTriangle`].
+pub type Triangle = CLBlastTriangle;
+/// [`CLBlastDiagonal`].
+pub type Diagonal = CLBlastDiagonal;
+/// [`CLBlastSide`].
+pub type Side = CLBlastSide;
+/// [`CLBlastKernelMode`].
+pub type KernelMode = CLBlastKernelMode;
+/// [`CLBlastPrecision`].
+pub type Precision = CLBlastPrecision;
+
+const _: () = {
+  const fn derives<T: std::fmt::Debug + Clone + Copy + Eq + std::hash::Hash + Ord>() {}
+  derives::<Layout>();
+  derives::<Transpose>();
+  derives::<Triangle>();
+  derives::<Diagonal>();
+  derives::<Side>();
+  derives::<KernelMode>();
+  derives::<Precision>();
+};
+
+#[cfg(feature = "serde")]
+const _: () = {
+  const fn serde<T: serde::Serialize + for<'de> serde::Deserialize<'de>>() {}
+  serde::<Layout>();
+  serde::<Transpose>();
+  serde::<Triangle>();
+  serde::<Diagonal>();
+  serde::<Side>();
+  serde::<KernelMode>();
+  serde::<Precision>();
+};
+
+impl CLBlastLayout {
+  /// The other storage order.
+  pub const fn opposite(self) -> CLBlastLayout {
+    match self {
+      CLBlastLayout::RowMajor => CLBlastLayout::ColMajor,
+      CLBlastLayout::ColMajor => CLBlastLayout::RowMajor,
+    }
+  }
+}
+
+impl CLBlastTranspose {
+  /// `true` for `Yes` and `Conjugate`.
+  pub const fn is_transposed(self) -> bool {
+    !matches!(self, CLBlastTranspose::No)
+  }
+
+  /// `true` only for `Conjugate`.
+  pub const fn is_conjugate(self) -> bool {
+    matches!(self, CLBlastTranspose::Conjugate)
+  }
+}
+
+impl CLBlastTriangle {
+  /// The other triangle. Transposing a triangular matrix, or reading it in
+  /// the other layout, swaps which triangle holds the data.
+  pub const fn opposite(self) -> CLBlastTriangle {
+    match self {
+      CLBlastTriangle::Upper => CLBlastTriangle::Lower,
+      CLBlastTriangle::Lower => CLBlastTriangle::Upper,
+    }
+  }
+}
+
+impl CLBlastDiagonal {
+  /// `true` if the diagonal is assumed to be all ones.
+  pub const fn is_unit(self) -> bool {
+    matches!(self, CLBlastDiagonal::Unit)
+  }
+}
+
+impl CLBlastSide {
+  /// The other side, e.g. to turn `A * X = B` into `X^T * A^T = B^T`.
+  pub const fn swap(self) -> CLBlastSide {
+    match self {
+      CLBlastSide::Left => CLBlastSide::Right,
+      CLBlastSide::Right => CLBlastSide::Left,
+    }
+  }
+}
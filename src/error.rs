@@ -0,0 +1,292 @@
+//! Error type shared by the validated overlays and the generated wrappers.
+
+use thiserror::Error;
+
+use crate::clblast_sys::CLBlastStatusCode;
+
+/// Errors returned by the wrappers in this crate.
+///
+/// Failed statuses are split by range (see [`classify_status`]): codes in
+/// OpenCL's range (`-1` to `-999`, e.g. `-5` for `CL_OUT_OF_RESOURCES`)
+/// become [`CLBlastError::OpenCL`], while each CLBlast-specific code
+/// (`-1000` and below) has a variant of the same name whose `Display` is
+/// the upstream name (e.g. `"CLBlastInvalidDimension"`), so callers can
+/// `match` instead of parsing messages. The remaining variants come from
+/// this crate's own checks or from `ocl`.
+#[derive(Debug, Error)]
+pub enum CLBlastError {
+  /// An OpenCL error passed through by CLBlast, e.g. `-5`
+  /// (`CL_OUT_OF_RESOURCES`) when the device runs out of memory.
+  #[error("{} ({})", crate::status::opencl_error_name(*.0), .0)]
+  OpenCL(i32),
+  /// A buffer is too small for the requested length, offset and stride.
+  /// CLBlast reports this as `CL_INVALID_BUFFER_SIZE` (`-61`), but it gets
+  /// its own variant since this crate's checks raise it too.
+  #[error("CLBlastInvalidBufferSize")]
+  InvalidBufferSize,
+  /// The routine is not implemented for this precision or configuration.
+  #[error("CLBlastNotImplemented")]
+  NotImplemented,
+  /// Matrix `a` is not a valid OpenCL buffer.
+  #[error("CLBlastInvalidMatrixA")]
+  InvalidMatrixA,
+  /// Matrix `b` is not a valid OpenCL buffer.
+  #[error("CLBlastInvalidMatrixB")]
+  InvalidMatrixB,
+  /// Matrix `c` is not a valid OpenCL buffer.
+  #[error("CLBlastInvalidMatrixC")]
+  InvalidMatrixC,
+  /// Vector `x` is not a valid OpenCL buffer.
+  #[error("CLBlastInvalidVectorX")]
+  InvalidVectorX,
+  /// Vector `y` is not a valid OpenCL buffer.
+  #[error("CLBlastInvalidVectorY")]
+  InvalidVectorY,
+  /// Matrix dimensions do not agree (e.g. inner dimensions of a product).
+  #[error("CLBlastInvalidDimension")]
+  InvalidDimension,
+  /// A dimension or leading dimension exceeds `i32::MAX`, the largest value
+  /// CLBlast's 32-bit kernel indexing supports.
+  #[error("dimension exceeds i32::MAX")]
+  DimensionTooLarge,
+  /// Leading dimension of matrix `a` is smaller than its contiguous dimension.
+  #[error("CLBlastInvalidLeadDimA")]
+  InvalidLeadDimA,
+  /// Leading dimension of matrix `b` is smaller than its contiguous dimension.
+  #[error("CLBlastInvalidLeadDimB")]
+  InvalidLeadDimB,
+  /// Leading dimension of matrix `c` is smaller than its contiguous dimension.
+  #[error("CLBlastInvalidLeadDimC")]
+  InvalidLeadDimC,
+  /// Increment of vector `x` is zero.
+  #[error("CLBlastInvalidIncrementX")]
+  InvalidIncrementX,
+  /// Increment of vector `y` is zero.
+  #[error("CLBlastInvalidIncrementY")]
+  InvalidIncrementY,
+  /// Buffer `a` is too small for the matrix.
+  #[error("CLBlastInsufficientMemoryA")]
+  InsufficientMemoryA,
+  /// Buffer `b` is too small for the matrix.
+  #[error("CLBlastInsufficientMemoryB")]
+  InsufficientMemoryB,
+  /// Buffer `c` is too small for the matrix.
+  #[error("CLBlastInsufficientMemoryC")]
+  InsufficientMemoryC,
+  /// Buffer `x` is too small for the vector.
+  #[error("CLBlastInsufficientMemoryX")]
+  InsufficientMemoryX,
+  /// Buffer `y` is too small for the vector.
+  #[error("CLBlastInsufficientMemoryY")]
+  InsufficientMemoryY,
+  /// The temporary buffer is too small (see also `TempBufferTooSmall`).
+  #[error("CLBlastInsufficientMemoryTemp")]
+  InsufficientMemoryTemp,
+  /// Batch count is zero or the per-batch argument lists differ in length.
+  #[error("CLBlastInvalidBatchCount")]
+  InvalidBatchCount,
+  /// `OverrideParameters` was given an unknown kernel name.
+  #[error("CLBlastInvalidOverrideKernel")]
+  InvalidOverrideKernel,
+  /// `OverrideParameters` is missing a parameter of the kernel.
+  #[error("CLBlastMissingOverrideParameter")]
+  MissingOverrideParameter,
+  /// The kernel parameters need more local memory than the device has.
+  #[error("CLBlastInvalidLocalMemUsage")]
+  InvalidLocalMemUsage,
+  /// The device does not support half precision (`cl_khr_fp16`).
+  #[error("CLBlastNoHalfPrecision")]
+  NoHalfPrecision,
+  /// The device does not support double precision (`cl_khr_fp64`).
+  #[error("CLBlastNoDoublePrecision")]
+  NoDoublePrecision,
+  /// The scalar result buffer is not a valid OpenCL buffer.
+  #[error("CLBlastInvalidVectorScalar")]
+  InvalidVectorScalar,
+  /// The scalar result buffer is too small.
+  #[error("CLBlastInsufficientMemoryScalar")]
+  InsufficientMemoryScalar,
+  /// No tuning parameters were found for the device.
+  #[error("CLBlastDatabaseError")]
+  DatabaseError,
+  /// CLBlast reported an unspecified error.
+  #[error("CLBlastUnknownError")]
+  UnknownError,
+  /// CLBlast hit an unexpected internal error.
+  #[error("CLBlastUnexpectedError")]
+  UnexpectedError,
+  /// Two arguments that must be distinct buffers are the same `cl_mem`
+  /// (only checked with the `alias-check` feature).
+  #[error("CLBlastAliasingViolation")]
+  AliasingViolation,
+  /// The scratch buffer passed to a `*_with_temp_buffer` routine is smaller
+  /// than the `needed` bytes reported by the matching `*TempBufferSize`
+  /// query; reallocate it and retry.
+  #[error("CLBlastInsufficientMemoryTemp: {needed} bytes needed")]
+  TempBufferTooSmall { needed: usize },
+  /// A CLBlast-specific code without a variant of its own, e.g. one added
+  /// by a newer `clblast_c.h` than this crate knows about.
+  #[error("CLBlast{0:?}")]
+  Blast(CLBlastStatusCode),
+  /// A status code that is not a `CLBlastStatusCode` value (see
+  /// [`classify_status`]).
+  #[error("CLBlast status {0}")]
+  Unknown(i32),
+  /// Error raised by `ocl` while handling queues, events or buffers.
+  #[error(transparent)]
+  Ocl(#[from] ocl::Error),
+}
+
+/// Lowest code in the range CLBlast shares with OpenCL.
+const OPENCL_MIN: i32 = -999;
+
+/// The error for a failed raw status: [`CLBlastError::OpenCL`] for codes in
+/// OpenCL's range (`-1` to `-999`, except `-61`, see
+/// [`CLBlastError::InvalidBufferSize`]), the matching variant for
+/// CLBlast-specific codes, and `Unknown(code)` for anything else, including
+/// `0` (`Success` is not an error).
+pub fn classify_status(code: i32) -> CLBlastError {
+  if (OPENCL_MIN..0).contains(&code) && code != CLBlastStatusCode::InvalidBufferSize as i32 {
+    return CLBlastError::OpenCL(code);
+  }
+  crate::all_status_codes()
+    .iter()
+    .find(|(status, _)| *status as i32 == code && code != 0)
+    .map_or(CLBlastError::Unknown(code), |(status, _)| {
+      CLBlastError::from(*status)
+    })
+}
+
+/// Same as [`classify_status`] on the code's value.
+impl From<CLBlastStatusCode> for CLBlastError {
+  fn from(code: CLBlastStatusCode) -> CLBlastError {
+    let raw = code as i32;
+    if raw == 0 {
+      return CLBlastError::Unknown(0);
+    }
+    if (OPENCL_MIN..0).contains(&raw) && code != CLBlastStatusCode::InvalidBufferSize {
+      return CLBlastError::OpenCL(raw);
+    }
+    match code {
+      CLBlastStatusCode::NotImplemented => CLBlastError::NotImplemented,
+      CLBlastStatusCode::InvalidMatrixA => CLBlastError::InvalidMatrixA,
+      CLBlastStatusCode::InvalidMatrixB => CLBlastError::InvalidMatrixB,
+      CLBlastStatusCode::InvalidMatrixC => CLBlastError::InvalidMatrixC,
+      CLBlastStatusCode::InvalidVectorX => CLBlastError::InvalidVectorX,
+      CLBlastStatusCode::InvalidVectorY => CLBlastError::InvalidVectorY,
+      CLBlastStatusCode::InvalidIncrementX => CLBlastError::InvalidIncrementX,
+      CLBlastStatusCode::InvalidIncrementY => CLBlastError::InvalidIncrementY,
+      CLBlastStatusCode::InsufficientMemoryA => CLBlastError::InsufficientMemoryA,
+      CLBlastStatusCode::InsufficientMemoryB => CLBlastError::InsufficientMemoryB,
+      CLBlastStatusCode::InsufficientMemoryC => CLBlastError::InsufficientMemoryC,
+      CLBlastStatusCode::InsufficientMemoryX => CLBlastError::InsufficientMemoryX,
+      CLBlastStatusCode::InsufficientMemoryY => CLBlastError::InsufficientMemoryY,
+      CLBlastStatusCode::InsufficientMemoryTemp => CLBlastError::InsufficientMemoryTemp,
+      CLBlastStatusCode::InvalidOverrideKernel => CLBlastError::InvalidOverrideKernel,
+      CLBlastStatusCode::MissingOverrideParameter => CLBlastError::MissingOverrideParameter,
+      CLBlastStatusCode::InvalidLocalMemUsage => CLBlastError::InvalidLocalMemUsage,
+      CLBlastStatusCode::InvalidVectorScalar => CLBlastError::InvalidVectorScalar,
+      CLBlastStatusCode::InsufficientMemoryScalar => CLBlastError::InsufficientMemoryScalar,
+      CLBlastStatusCode::DatabaseError => CLBlastError::DatabaseError,
+      CLBlastStatusCode::UnknownError => CLBlastError::UnknownError,
+      CLBlastStatusCode::UnexpectedError => CLBlastError::UnexpectedError,
+      CLBlastStatusCode::InvalidBufferSize => CLBlastError::InvalidBufferSize,
+      CLBlastStatusCode::InvalidDimension => CLBlastError::InvalidDimension,
+      CLBlastStatusCode::InvalidLeadDimA => CLBlastError::InvalidLeadDimA,
+      CLBlastStatusCode::InvalidLeadDimB => CLBlastError::InvalidLeadDimB,
+      CLBlastStatusCode::InvalidLeadDimC => CLBlastError::InvalidLeadDimC,
+      CLBlastStatusCode::InvalidBatchCount => CLBlastError::InvalidBatchCount,
+      CLBlastStatusCode::NoHalfPrecision => CLBlastError::NoHalfPrecision,
+      CLBlastStatusCode::NoDoublePrecision => CLBlastError::NoDoublePrecision,
+      other => CLBlastError::Blast(other),
+    }
+  }
+}
+
+/// Keeps `ocl::Result` code working: the error is carried as its message.
+impl From<CLBlastError> for ocl::Error {
+  fn from(err: CLBlastError) -> ocl::Error {
+    match err {
+      CLBlastError::Ocl(err) => err,
+      other => ocl::Error::from(other.to_string()),
+    }
+  }
+}
+
+impl CLBlastError {
+  /// The error for a raw status integer, e.g. one read back across FFI.
+  /// See [`classify_status`].
+  pub fn from_raw(code: i32) -> CLBlastError {
+    classify_status(code)
+  }
+
+  /// The `CLBlastStatusCode` this error corresponds to, e.g. for returning
+  /// it across a C API. `None` for errors raised by this crate's own checks
+  /// (`DimensionTooLarge`, `AliasingViolation`), `Unknown` codes, OpenCL
+  /// codes CLBlast does not list and `ocl` errors.
+  pub fn status_code(&self) -> Option<CLBlastStatusCode> {
+    Some(match self {
+      CLBlastError::NotImplemented => CLBlastStatusCode::NotImplemented,
+      CLBlastError::InvalidMatrixA => CLBlastStatusCode::InvalidMatrixA,
+      CLBlastError::InvalidMatrixB => CLBlastStatusCode::InvalidMatrixB,
+      CLBlastError::InvalidMatrixC => CLBlastStatusCode::InvalidMatrixC,
+      CLBlastError::InvalidVectorX => CLBlastStatusCode::InvalidVectorX,
+      CLBlastError::InvalidVectorY => CLBlastStatusCode::InvalidVectorY,
+      CLBlastError::InvalidIncrementX => CLBlastStatusCode::InvalidIncrementX,
+      CLBlastError::InvalidIncrementY => CLBlastStatusCode::InvalidIncrementY,
+      CLBlastError::InsufficientMemoryA => CLBlastStatusCode::InsufficientMemoryA,
+      CLBlastError::InsufficientMemoryB => CLBlastStatusCode::InsufficientMemoryB,
+      CLBlastError::InsufficientMemoryC => CLBlastStatusCode::InsufficientMemoryC,
+      CLBlastError::InsufficientMemoryX => CLBlastStatusCode::InsufficientMemoryX,
+      CLBlastError::InsufficientMemoryY => CLBlastStatusCode::InsufficientMemoryY,
+      CLBlastError::InsufficientMemoryTemp => CLBlastStatusCode::InsufficientMemoryTemp,
+      CLBlastError::InvalidOverrideKernel => CLBlastStatusCode::InvalidOverrideKernel,
+      CLBlastError::MissingOverrideParameter => CLBlastStatusCode::MissingOverrideParameter,
+      CLBlastError::InvalidLocalMemUsage => CLBlastStatusCode::InvalidLocalMemUsage,
+      CLBlastError::InvalidVectorScalar => CLBlastStatusCode::InvalidVectorScalar,
+      CLBlastError::InsufficientMemoryScalar => CLBlastStatusCode::InsufficientMemoryScalar,
+      CLBlastError::DatabaseError => CLBlastStatusCode::DatabaseError,
+      CLBlastError::UnknownError => CLBlastStatusCode::UnknownError,
+      CLBlastError::UnexpectedError => CLBlastStatusCode::UnexpectedError,
+      CLBlastError::InvalidBufferSize => CLBlastStatusCode::InvalidBufferSize,
+      CLBlastError::InvalidDimension => CLBlastStatusCode::InvalidDimension,
+      CLBlastError::InvalidLeadDimA => CLBlastStatusCode::InvalidLeadDimA,
+      CLBlastError::InvalidLeadDimB => CLBlastStatusCode::InvalidLeadDimB,
+      CLBlastError::InvalidLeadDimC => CLBlastStatusCode::InvalidLeadDimC,
+      CLBlastError::InvalidBatchCount => CLBlastStatusCode::InvalidBatchCount,
+      CLBlastError::NoHalfPrecision => CLBlastStatusCode::NoHalfPrecision,
+      CLBlastError::NoDoublePrecision => CLBlastStatusCode::NoDoublePrecision,
+      CLBlastError::TempBufferTooSmall { .. } => CLBlastStatusCode::InsufficientMemoryTemp,
+      CLBlastError::Blast(code) => *code,
+      CLBlastError::OpenCL(code) => {
+        return crate::all_status_codes()
+          .iter()
+          .map(|(status, _)| *status)
+          .find(|status| *status as i32 == *code);
+      }
+      CLBlastError::DimensionTooLarge
+      | CLBlastError::AliasingViolation
+      | CLBlastError::Unknown(_)
+      | CLBlastError::Ocl(_) => return None,
+    })
+  }
+
+  /// Integer code for FFI: the [`status_code`](Self::status_code) if there
+  /// is one, the raw value of `OpenCL` and `Unknown`, the OpenCL status of an `Ocl` error
+  /// (CLBlast passes OpenCL codes through unchanged), else `UnknownError`
+  /// (`-2040`).
+  pub fn as_i32(&self) -> i32 {
+    if let Some(code) = self.status_code() {
+      return code as i32;
+    }
+    match self {
+      CLBlastError::OpenCL(code) | CLBlastError::Unknown(code) => *code,
+      CLBlastError::Ocl(err) => err
+        .api_status()
+        .map_or(CLBlastStatusCode::UnknownError as i32, |status| {
+          status as i32
+        }),
+      _ => CLBlastStatusCode::UnknownError as i32,
+    }
+  }
+}
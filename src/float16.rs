@@ -0,0 +1,57 @@
+//! Half-precision (`h*`) scalars and buffer elements.
+//!
+//! CLBlast takes halves as raw `cl_half` bits. With the `f16` feature the
+//! generated `h*` wrappers take their scalars as `half::f16` instead, and
+//! [`F16`] makes `half::f16` usable as a buffer element (`OclPrm` cannot be
+//! implemented for `half::f16` itself outside `ocl`).
+
+use crate::clblast_sys::cl_half;
+
+/// Scalar type of the `h*` wrappers: `half::f16` with the `f16` feature,
+/// raw `cl_half` bits without.
+#[cfg(feature = "f16")]
+pub type Half = half::f16;
+/// Scalar type of the `h*` wrappers: `half::f16` with the `f16` feature,
+/// raw `cl_half` bits without.
+#[cfg(not(feature = "f16"))]
+pub type Half = cl_half;
+
+/// `1.0` and `0.0` as [`Half`]s.
+#[cfg(feature = "f16")]
+pub(crate) const HALF_ONE_ZERO: (Half, Half) = (half::f16::ONE, half::f16::ZERO);
+/// `1.0` and `0.0` as [`Half`]s (IEEE 754 half-precision bits).
+#[cfg(not(feature = "f16"))]
+pub(crate) const HALF_ONE_ZERO: (Half, Half) = (0x3c00, 0);
+
+/// The bits CLBlast expects for `x`.
+#[inline]
+pub(crate) fn half_bits(x: Half) -> cl_half {
+  #[cfg(feature = "f16")]
+  return x.to_bits();
+  #[cfg(not(feature = "f16"))]
+  return x;
+}
+
+/// `half::f16` as a buffer element, e.g. `Buffer<F16>` for `hgemm`.
+#[cfg(feature = "f16")]
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct F16(pub half::f16);
+
+// Safety: a plain 2-byte value with the layout of `cl_half`.
+#[cfg(feature = "f16")]
+unsafe impl ocl::OclPrm for F16 {}
+
+#[cfg(feature = "f16")]
+impl From<half::f16> for F16 {
+  fn from(x: half::f16) -> F16 {
+    F16(x)
+  }
+}
+
+#[cfg(feature = "f16")]
+impl From<F16> for half::f16 {
+  fn from(x: F16) -> half::f16 {
+    x.0
+  }
+}
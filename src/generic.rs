@@ -0,0 +1,245 @@
+//! Precision-generic entry points over the generated wrappers.
+//!
+//! [`ClblastFloat`] maps an element type to its CLBlast routines, so generic
+//! numeric code can call [`gemm`] instead of picking `sgemm`/`dgemm`/...
+//! itself. Complex elements are `ocl::prm::Float2`/`Double2` or, with the
+//! `complex` feature, `num_complex` numbers.
+//!
+//! `alpha` and `beta` are the element type's [`ClblastFloat::Scalar`]: the
+//! element type itself for `f32`/`f64`, and [`ComplexF32`]/[`ComplexF64`]
+//! for the complex precisions, whichever element type holds them.
+
+use ocl::prm::{Double2, Float2};
+use ocl::{Buffer, OclPrm};
+
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+use crate::error::CLBlastError;
+use crate::level3::{self, GemmShape};
+use crate::queue::AsQueue;
+use crate::{ComplexF32, ComplexF64, CoreEvent, Ld, Offset, complex_f32, complex_f64};
+
+/// Scalar (`alpha`, `beta`) types of the CLBlast routines.
+pub trait ClblastScalar: Copy {
+  /// `1`, e.g. `alpha` for a plain product.
+  const ONE: Self;
+  /// `0`, e.g. `beta` to overwrite the output.
+  const ZERO: Self;
+}
+
+impl ClblastScalar for f32 {
+  const ONE: f32 = 1.0;
+  const ZERO: f32 = 0.0;
+}
+
+impl ClblastScalar for f64 {
+  const ONE: f64 = 1.0;
+  const ZERO: f64 = 0.0;
+}
+
+impl ClblastScalar for ComplexF32 {
+  const ONE: ComplexF32 = complex_f32(1.0, 0.0);
+  const ZERO: ComplexF32 = complex_f32(0.0, 0.0);
+}
+
+impl ClblastScalar for ComplexF64 {
+  const ONE: ComplexF64 = complex_f64(1.0, 0.0);
+  const ZERO: ComplexF64 = complex_f64(0.0, 0.0);
+}
+
+/// Element types CLBlast has routines for: `f32`, `f64`, `Float2`/`Double2`
+/// and, with the `complex` feature, `Complex32`/`Complex64`.
+///
+/// Also the element bound of [`GemmBuilder`](crate::builder::GemmBuilder),
+/// whose default `alpha`/`beta` are [`ClblastScalar::ONE`]/`ZERO`.
+pub trait ClblastFloat: OclPrm {
+  /// Type of `alpha` and `beta` for this precision.
+  type Scalar: ClblastScalar;
+
+  /// The generated `gemm` wrapper of this precision, with every matrix a
+  /// `Buffer<Self>`.
+  #[allow(clippy::too_many_arguments)]
+  fn gemm<'a>(
+    queue: &impl AsQueue,
+    layout: CLBlastLayout,
+    a_transpose: CLBlastTranspose,
+    b_transpose: CLBlastTranspose,
+    m: usize,
+    n: usize,
+    k: usize,
+    alpha: Self::Scalar,
+    a_buffer: &Buffer<Self>,
+    a_offset: Offset,
+    a_ld: Ld,
+    b_buffer: &Buffer<Self>,
+    b_offset: Offset,
+    b_ld: Ld,
+    beta: Self::Scalar,
+    c_buffer: &Buffer<Self>,
+    c_offset: Offset,
+    c_ld: Ld,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> crate::Result<Option<CoreEvent>>;
+
+  /// The validated `level3` overlay of this precision (`level3::sgemm`,
+  /// ...), with the dimensions taken from `shape`. Used by
+  /// [`GemmBuilder`](crate::builder::GemmBuilder).
+  #[allow(clippy::too_many_arguments)]
+  fn gemm_validated<'a>(
+    queue: &impl AsQueue,
+    shape: GemmShape,
+    alpha: Self::Scalar,
+    a_buffer: &Buffer<Self>,
+    a_offset: usize,
+    b_buffer: &Buffer<Self>,
+    b_offset: usize,
+    beta: Self::Scalar,
+    c_buffer: &Buffer<Self>,
+    c_offset: usize,
+    wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  ) -> Result<Option<CoreEvent>, CLBlastError>;
+}
+
+macro_rules! impl_clblast_float {
+  ($t:ty, $scalar:ty, $gemm:ident, $validated:path) => {
+    impl ClblastFloat for $t {
+      type Scalar = $scalar;
+
+      fn gemm<'a>(
+        queue: &impl AsQueue,
+        layout: CLBlastLayout,
+        a_transpose: CLBlastTranspose,
+        b_transpose: CLBlastTranspose,
+        m: usize,
+        n: usize,
+        k: usize,
+        alpha: $scalar,
+        a_buffer: &Buffer<$t>,
+        a_offset: Offset,
+        a_ld: Ld,
+        b_buffer: &Buffer<$t>,
+        b_offset: Offset,
+        b_ld: Ld,
+        beta: $scalar,
+        c_buffer: &Buffer<$t>,
+        c_offset: Offset,
+        c_ld: Ld,
+        wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+      ) -> crate::Result<Option<CoreEvent>> {
+        crate::$gemm(
+          queue,
+          layout,
+          a_transpose,
+          b_transpose,
+          m,
+          n,
+          k,
+          alpha,
+          a_buffer,
+          a_offset,
+          a_ld,
+          b_buffer,
+          b_offset,
+          b_ld,
+          beta,
+          c_buffer,
+          c_offset,
+          c_ld,
+          wait_for,
+        )
+      }
+
+      fn gemm_validated<'a>(
+        queue: &impl AsQueue,
+        shape: GemmShape,
+        alpha: $scalar,
+        a_buffer: &Buffer<$t>,
+        a_offset: usize,
+        b_buffer: &Buffer<$t>,
+        b_offset: usize,
+        beta: $scalar,
+        c_buffer: &Buffer<$t>,
+        c_offset: usize,
+        wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+      ) -> Result<Option<CoreEvent>, CLBlastError> {
+        $validated(
+          queue,
+          shape.layout,
+          shape.a_transpose,
+          shape.b_transpose,
+          shape.m,
+          shape.n,
+          shape.k,
+          alpha,
+          a_buffer,
+          a_offset,
+          shape.a_ld,
+          b_buffer,
+          b_offset,
+          shape.b_ld,
+          beta,
+          c_buffer,
+          c_offset,
+          shape.c_ld,
+          wait_for,
+        )
+      }
+    }
+  };
+}
+
+impl_clblast_float!(f32, f32, sgemm, level3::sgemm);
+impl_clblast_float!(f64, f64, dgemm, level3::dgemm);
+impl_clblast_float!(Float2, ComplexF32, cgemm, level3::cgemm);
+impl_clblast_float!(Double2, ComplexF64, zgemm, level3::zgemm);
+#[cfg(feature = "complex")]
+impl_clblast_float!(num_complex::Complex32, ComplexF32, cgemm, level3::cgemm);
+#[cfg(feature = "complex")]
+impl_clblast_float!(num_complex::Complex64, ComplexF64, zgemm, level3::zgemm);
+
+/// `C = alpha * op(A) * op(B) + beta * C` in the precision of `T`; see
+/// [`ClblastFloat::gemm`]. Not validated: use the `level3` overlays (or
+/// [`crate::builder::GemmBuilder`]) for checked calls.
+#[allow(clippy::too_many_arguments)]
+pub fn gemm<'a, T: ClblastFloat>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: T::Scalar,
+  a_buffer: &Buffer<T>,
+  a_offset: Offset,
+  a_ld: Ld,
+  b_buffer: &Buffer<T>,
+  b_offset: Offset,
+  b_ld: Ld,
+  beta: T::Scalar,
+  c_buffer: &Buffer<T>,
+  c_offset: Offset,
+  c_ld: Ld,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> crate::Result<Option<CoreEvent>> {
+  T::gemm(
+    queue,
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    beta,
+    c_buffer,
+    c_offset,
+    c_ld,
+    wait_for,
+  )
+}
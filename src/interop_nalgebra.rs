@@ -0,0 +1,81 @@
+//! Host-matrix front ends for `nalgebra` (feature `nalgebra`).
+//!
+//! Like [`crate::interop_ndarray`], each call uploads its inputs into fresh
+//! device buffers and blocks on the read-back. `DMatrix` storage is dense
+//! column-major, so it is uploaded as-is and passed as `ColMajor`.
+
+use nalgebra::DMatrix;
+use ocl::{Buffer, Queue};
+
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+use crate::error::CLBlastError;
+use crate::level3;
+
+/// Upload the column-major storage of `matrix`.
+fn upload(queue: &Queue, matrix: &DMatrix<f32>) -> Result<Buffer<f32>, CLBlastError> {
+  Ok(
+    Buffer::builder()
+      .queue(queue.clone())
+      .len(matrix.len())
+      .copy_host_slice(matrix.as_slice())
+      .build()?,
+  )
+}
+
+/// `A * B` computed with `sgemm` on `queue`.
+///
+/// `a` is `m x k` and `b` is `k x n`; each leading dimension is the row
+/// count of its matrix. Returns `InvalidDimension` when the inner
+/// dimensions differ. Empty products return an `m x n` zero matrix without
+/// touching the device.
+pub fn gemm_nalgebra(
+  queue: &Queue,
+  a: &DMatrix<f32>,
+  b: &DMatrix<f32>,
+) -> Result<DMatrix<f32>, CLBlastError> {
+  let (m, k) = a.shape();
+  let (kb, n) = b.shape();
+  if k != kb {
+    return Err(CLBlastError::InvalidDimension);
+  }
+  if m == 0 || n == 0 || k == 0 {
+    return Ok(DMatrix::zeros(m, n));
+  }
+
+  let a_buffer = upload(queue, a)?;
+  let b_buffer = upload(queue, b)?;
+  let c_buffer = Buffer::<f32>::builder()
+    .queue(queue.clone())
+    .len(m * n)
+    .fill_val(0.0)
+    .build()?;
+  let event = level3::sgemm(
+    queue,
+    CLBlastLayout::ColMajor,
+    CLBlastTranspose::No,
+    CLBlastTranspose::No,
+    m,
+    n,
+    k,
+    1.0,
+    &a_buffer,
+    0,
+    m,
+    &b_buffer,
+    0,
+    k,
+    0.0,
+    &c_buffer,
+    0,
+    m,
+    &[],
+  )?;
+
+  let mut host = vec![0.0f32; m * n];
+  let mut read = c_buffer.read(&mut host).queue(queue);
+  if let Some(event) = &event {
+    read = read.ewait(event);
+  }
+  read.enq()?;
+  Ok(DMatrix::from_vec(m, n, host))
+}
@@ -0,0 +1,112 @@
+//! Host-matrix front ends for quick experiments (feature `ndarray`).
+//!
+//! Each call uploads its inputs into fresh device buffers, runs the routine,
+//! blocks on the read-back and drops the buffers again. Keep data in
+//! [`ocl::Buffer`]s and use the regular wrappers when it stays on the device.
+
+use ndarray::{Array2, ArrayView2};
+use ocl::{Buffer, Queue};
+
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+use crate::error::CLBlastError;
+use crate::level3;
+
+/// Upload `view` for a row-major `gemm` operand, returning the buffer, the
+/// transpose to apply and the leading dimension.
+///
+/// Row-major views are uploaded as they are and column-major ones (e.g.
+/// `a.t()`) as their row-major transpose, so neither is copied on the
+/// host; any other stride pattern is packed first.
+fn upload(
+  queue: &Queue,
+  view: ArrayView2<f32>,
+) -> Result<(Buffer<f32>, CLBlastTranspose, usize), CLBlastError> {
+  let (rows, cols) = view.dim();
+  let (packed, transpose, ld);
+  let host = if let Some(host) = view.as_slice() {
+    (transpose, ld) = (CLBlastTranspose::No, cols);
+    host
+  } else if let Some(host) = view.t().to_slice() {
+    (transpose, ld) = (CLBlastTranspose::Yes, rows);
+    host
+  } else {
+    packed = view.as_standard_layout();
+    (transpose, ld) = (CLBlastTranspose::No, cols);
+    packed.as_slice().expect("standard layout is contiguous")
+  };
+  let buffer = Buffer::builder()
+    .queue(queue.clone())
+    .len(host.len())
+    .copy_host_slice(host)
+    .build()?;
+  Ok((buffer, transpose, ld))
+}
+
+/// `A * B` computed with `sgemm` on `queue`.
+///
+/// `a` is `m x k` and `b` is `k x n`. Row-major (`is_standard_layout()`)
+/// and column-major views go to the device without a host copy, the latter
+/// through `sgemm`'s transpose flag; other strides (e.g. `s![.., ..;2]`)
+/// are packed first. Returns `InvalidDimension` when the inner dimensions
+/// differ. Empty products return an `m x n` zero matrix without touching
+/// the device.
+pub fn gemm_ndarray(
+  queue: &Queue,
+  a: ArrayView2<f32>,
+  b: ArrayView2<f32>,
+) -> Result<Array2<f32>, CLBlastError> {
+  let (m, k) = a.dim();
+  let (kb, n) = b.dim();
+  if k != kb {
+    return Err(CLBlastError::InvalidDimension);
+  }
+  if m == 0 || n == 0 || k == 0 {
+    return Ok(Array2::zeros((m, n)));
+  }
+
+  let (a_buffer, a_transpose, a_ld) = upload(queue, a)?;
+  let (b_buffer, b_transpose, b_ld) = upload(queue, b)?;
+  let c_buffer = Buffer::<f32>::builder()
+    .queue(queue.clone())
+    .len(m * n)
+    .fill_val(0.0)
+    .build()?;
+  let event = level3::sgemm(
+    queue,
+    CLBlastLayout::RowMajor,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    1.0,
+    &a_buffer,
+    0,
+    a_ld,
+    &b_buffer,
+    0,
+    b_ld,
+    0.0,
+    &c_buffer,
+    0,
+    n,
+    &[],
+  )?;
+
+  let mut host = vec![0.0f32; m * n];
+  let mut read = c_buffer.read(&mut host).queue(queue);
+  if let Some(event) = &event {
+    read = read.ewait(event);
+  }
+  read.enq()?;
+  Ok(Array2::from_shape_vec((m, n), host).expect("m * n elements"))
+}
+
+/// [`gemm_ndarray`] under its earlier name.
+pub fn sgemm_host(
+  queue: &Queue,
+  a: ArrayView2<f32>,
+  b: ArrayView2<f32>,
+) -> Result<Array2<f32>, CLBlastError> {
+  gemm_ndarray(queue, a, b)
+}
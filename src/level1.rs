@@ -0,0 +1,594 @@
+//! Validated Level-1 overlays.
+//!
+//! Each function checks that `offset + (n - 1) * inc` stays inside every
+//! buffer it touches, then forwards to the generated wrapper of the same
+//! name. Queue, wait-list and returned-event behaviour is unchanged.
+//!
+//! The `*_scalar` reductions are the exception: they allocate a one-element
+//! device buffer on `queue`, run the routine into it and block until the
+//! result has been read back to the host. The index routines (`isamax`,
+//! `ismin`, ...) work the same way and return the index as a `usize`.
+
+use ocl::{Buffer, OclPrm, Queue};
+
+use crate::error::CLBlastError;
+#[cfg(feature = "batched")]
+use crate::mem::{Element, precision};
+use crate::queue::AsQueue;
+use crate::validate::check_vector;
+use crate::{CoreEvent, Offset};
+
+/// `x = alpha * x`, validated.
+pub fn sscal<'a>(
+  queue: &impl AsQueue,
+  n: usize,
+  alpha: f32,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  Ok(crate::sscal(
+    queue,
+    n,
+    alpha,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    wait_for,
+  )?)
+}
+
+/// `y = x`, validated.
+#[allow(clippy::too_many_arguments)]
+pub fn scopy<'a>(
+  queue: &impl AsQueue,
+  n: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  Ok(crate::scopy(
+    queue,
+    n,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// `x <-> y`, validated.
+#[allow(clippy::too_many_arguments)]
+pub fn sswap<'a>(
+  queue: &impl AsQueue,
+  n: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  Ok(crate::sswap(
+    queue,
+    n,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// `y = alpha * x + y`, validated.
+#[allow(clippy::too_many_arguments)]
+pub fn saxpy<'a>(
+  queue: &impl AsQueue,
+  n: usize,
+  alpha: f32,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  Ok(crate::saxpy(
+    queue,
+    n,
+    alpha,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// [`sscal`] returning `x_buffer` for chaining.
+///
+/// The event is dropped: later commands observe the result only on an
+/// in-order queue (the default). Use [`sscal`] when ordering must be
+/// expressed through events.
+pub fn sscal_chain<'b>(
+  queue: &impl AsQueue,
+  n: usize,
+  alpha: f32,
+  x_buffer: &'b Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+) -> Result<&'b Buffer<f32>, CLBlastError> {
+  sscal(queue, n, alpha, x_buffer, x_offset, x_inc, &[])?;
+  Ok(x_buffer)
+}
+
+/// [`saxpy`] returning `y_buffer` for chaining; see [`sscal_chain`] for the
+/// ordering caveat.
+#[allow(clippy::too_many_arguments)]
+pub fn saxpy_chain<'b>(
+  queue: &impl AsQueue,
+  n: usize,
+  alpha: f32,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &'b Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+) -> Result<&'b Buffer<f32>, CLBlastError> {
+  saxpy(
+    queue,
+    n,
+    alpha,
+    x_buffer,
+    x_offset,
+    x_inc,
+    y_buffer,
+    y_offset,
+    y_inc,
+    &[],
+  )?;
+  Ok(y_buffer)
+}
+
+/// [`scopy`] returning `y_buffer` for chaining; see [`sscal_chain`] for the
+/// ordering caveat.
+#[allow(clippy::too_many_arguments)]
+pub fn scopy_chain<'b>(
+  queue: &impl AsQueue,
+  n: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &'b Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+) -> Result<&'b Buffer<f32>, CLBlastError> {
+  scopy(
+    queue,
+    n,
+    x_buffer,
+    x_offset,
+    x_inc,
+    y_buffer,
+    y_offset,
+    y_inc,
+    &[],
+  )?;
+  Ok(y_buffer)
+}
+
+/// [`sswap`] returning both buffers for chaining; see [`sscal_chain`] for
+/// the ordering caveat.
+#[allow(clippy::too_many_arguments)]
+pub fn sswap_chain<'b>(
+  queue: &impl AsQueue,
+  n: usize,
+  x_buffer: &'b Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &'b Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+) -> Result<(&'b Buffer<f32>, &'b Buffer<f32>), CLBlastError> {
+  sswap(
+    queue,
+    n,
+    x_buffer,
+    x_offset,
+    x_inc,
+    y_buffer,
+    y_offset,
+    y_inc,
+    &[],
+  )?;
+  Ok((x_buffer, y_buffer))
+}
+
+macro_rules! axpy_batched {
+  ($(#[$doc:meta])* $name:ident $(<$t:ident: $elem_precision:ident>)?, $elem:ty, $scalar:ty) => {
+    $(#[$doc])*
+    #[cfg(feature = "batched")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn $name<'a $(, $t: Element<Precision = precision::$elem_precision>)?>(
+      queue: &impl AsQueue,
+      n: usize,
+      alphas: &[$scalar],
+      x_buffer: &Buffer<$elem>,
+      x_offsets: &[usize],
+      x_inc: usize,
+      y_buffer: &Buffer<$elem>,
+      y_offsets: &[usize],
+      y_inc: usize,
+      wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+    ) -> Result<Option<CoreEvent>, CLBlastError> {
+      let batch_count = alphas.len();
+      if batch_count == 0 || x_offsets.len() != batch_count || y_offsets.len() != batch_count {
+        return Err(CLBlastError::InvalidBatchCount);
+      }
+      for (&x_offset, &y_offset) in x_offsets.iter().zip(y_offsets) {
+        check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+        check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+      }
+      // Safety: CLBlast reads `batch_count` entries from each slice, all of
+      // which exist and outlive the call.
+      Ok(unsafe {
+        crate::$name(
+          queue,
+          n,
+          alphas.as_ptr().cast(),
+          x_buffer,
+          x_offsets.as_ptr(),
+          x_inc,
+          y_buffer,
+          y_offsets.as_ptr(),
+          y_inc,
+          batch_count,
+          wait_for,
+        )
+      }?)
+    }
+  };
+}
+
+axpy_batched!(
+  /// `y_i = alphas[i] * x_i + y_i` for every batch entry `i` in one launch,
+  /// validated. Vector `i` of `x` (`y`) starts at `x_offsets[i]`
+  /// (`y_offsets[i]`); `n` and the increments are shared.
+  ///
+  /// The batch size is the length of the slices, which must be equal and
+  /// non-zero, otherwise `InvalidBatchCount` is returned before anything is
+  /// enqueued. Every batch entry is checked against its buffer.
+  saxpy_batched,
+  f32,
+  f32
+);
+axpy_batched!(
+  /// Double-precision [`saxpy_batched`], validated the same way.
+  daxpy_batched,
+  f64,
+  f64
+);
+axpy_batched!(
+  /// Single-complex [`saxpy_batched`], validated the same way.
+  caxpy_batched<T: ComplexSingle>,
+  T,
+  crate::ComplexF32
+);
+axpy_batched!(
+  /// Double-complex [`saxpy_batched`], validated the same way.
+  zaxpy_batched<T: ComplexDouble>,
+  T,
+  crate::ComplexF64
+);
+
+/// Run a reduction into a fresh one-element buffer and read the result back.
+fn reduce_to_host<T: OclPrm>(
+  queue: &Queue,
+  enqueue: impl FnOnce(&Buffer<T>) -> crate::Result<Option<CoreEvent>>,
+) -> Result<T, CLBlastError> {
+  let out = Buffer::<T>::builder()
+    .queue(queue.clone())
+    .len(1)
+    .fill_val(T::default())
+    .build()?;
+  let event = enqueue(&out)?;
+  let mut host = [T::default()];
+  let mut read = out.read(&mut host[..]).queue(queue);
+  if let Some(event) = &event {
+    read = read.ewait(event);
+  }
+  read.enq()?;
+  Ok(host[0])
+}
+
+/// Dot product `x^T * y` returned as a host scalar, validated.
+///
+/// Blocks until the result is available. Returns `0.0` for `n == 0`.
+#[allow(clippy::too_many_arguments)]
+pub fn sdot_scalar(
+  queue: &Queue,
+  n: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+) -> Result<f32, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  if n == 0 {
+    return Ok(0.0);
+  }
+  reduce_to_host(queue, |dot| {
+    crate::sdot(
+      queue,
+      n,
+      dot,
+      Offset(0),
+      x_buffer,
+      Offset(x_offset),
+      x_inc,
+      y_buffer,
+      Offset(y_offset),
+      y_inc,
+      &[],
+    )
+  })
+}
+
+macro_rules! reduce_scalar {
+  ($(#[$doc:meta])* $name:ident => $routine:ident, $elem:ty) => {
+    $(#[$doc])*
+    ///
+    /// Blocks until the result is available. Returns `0.0` for `n == 0`.
+    pub fn $name(
+      queue: &Queue,
+      n: usize,
+      x_buffer: &Buffer<$elem>,
+      x_offset: usize,
+      x_inc: usize,
+    ) -> Result<$elem, CLBlastError> {
+      check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+      if n == 0 {
+        return Ok(0.0);
+      }
+      reduce_to_host(queue, |out| {
+        crate::$routine(
+          queue,
+          n,
+          out,
+          Offset(0),
+          x_buffer,
+          Offset(x_offset),
+          x_inc,
+          &[],
+        )
+      })
+    }
+  };
+}
+
+reduce_scalar!(
+  /// Euclidean norm `||x||_2` returned as a host scalar, validated.
+  snrm2_scalar => snrm2,
+  f32
+);
+reduce_scalar!(
+  /// Euclidean norm `||x||_2` (double) returned as a host scalar, validated.
+  dnrm2_scalar => dnrm2,
+  f64
+);
+reduce_scalar!(
+  /// Sum of absolute values `sum |x_i|` returned as a host scalar, validated.
+  sasum_scalar => sasum,
+  f32
+);
+reduce_scalar!(
+  /// Sum of absolute values `sum |x_i|` (double) returned as a host scalar,
+  /// validated.
+  dasum_scalar => dasum,
+  f64
+);
+reduce_scalar!(
+  /// Plain sum `sum x_i` (a CLBlast extension) returned as a host scalar,
+  /// validated.
+  ssum_scalar => ssum,
+  f32
+);
+reduce_scalar!(
+  /// Plain sum `sum x_i` (double, a CLBlast extension) returned as a host
+  /// scalar, validated.
+  dsum_scalar => dsum,
+  f64
+);
+
+/// Scale `x` to unit 2-norm and return its previous norm, validated.
+///
+/// Runs `snrm2`, blocks on the read-back, then enqueues `sscal` by
+/// `1 / norm` on `queue` without waiting for it; later commands on an
+/// in-order queue observe the normalized vector. When the norm is `0.0`
+/// (including `n == 0`) the vector is left untouched.
+pub fn snormalize(
+  queue: &Queue,
+  n: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+) -> Result<f32, CLBlastError> {
+  let norm = snrm2_scalar(queue, n, x_buffer, x_offset, x_inc)?;
+  if norm != 0.0 {
+    crate::sscal(queue, n, 1.0 / norm, x_buffer, Offset(x_offset), x_inc, &[])?;
+  }
+  Ok(norm)
+}
+
+#[cfg(feature = "complex")]
+macro_rules! complex_dot_scalar {
+  ($(#[$doc:meta])* $name:ident => $routine:ident, $elem:ty) => {
+    $(#[$doc])*
+    ///
+    /// Blocks until the result is available. Returns zero for `n == 0`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn $name(
+      queue: &Queue,
+      n: usize,
+      x_buffer: &Buffer<$elem>,
+      x_offset: usize,
+      x_inc: usize,
+      y_buffer: &Buffer<$elem>,
+      y_offset: usize,
+      y_inc: usize,
+    ) -> Result<$elem, CLBlastError> {
+      check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+      check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+      if n == 0 {
+        return Ok(<$elem>::default());
+      }
+      reduce_to_host(queue, |dot| {
+        crate::$routine(
+          queue,
+          n,
+          dot,
+          Offset(0),
+          x_buffer,
+          Offset(x_offset),
+          x_inc,
+          y_buffer,
+          Offset(y_offset),
+          y_inc,
+          &[],
+        )
+      })
+    }
+  };
+}
+
+#[cfg(feature = "complex")]
+complex_dot_scalar!(
+  /// Unconjugated dot product `x^T * y` (single complex) returned as a host scalar, validated.
+  cdotu_scalar => cdotu,
+  num_complex::Complex32
+);
+#[cfg(feature = "complex")]
+complex_dot_scalar!(
+  /// Conjugated dot product `x^H * y` (single complex) returned as a host scalar, validated.
+  ///
+  /// Every element of `x` is conjugated before the product.
+  cdotc_scalar => cdotc,
+  num_complex::Complex32
+);
+#[cfg(feature = "complex")]
+complex_dot_scalar!(
+  /// Unconjugated dot product `x^T * y` (double complex) returned as a host scalar, validated.
+  zdotu_scalar => zdotu,
+  num_complex::Complex64
+);
+#[cfg(feature = "complex")]
+complex_dot_scalar!(
+  /// Conjugated dot product `x^H * y` (double complex) returned as a host scalar, validated.
+  ///
+  /// Every element of `x` is conjugated before the product.
+  zdotc_scalar => zdotc,
+  num_complex::Complex64
+);
+
+macro_rules! index_reduction {
+  ($(#[$doc:meta])* $name:ident => $routine:ident, $elem:ty) => {
+    $(#[$doc])*
+    ///
+    /// Blocks until the index has been read back from the one-element
+    /// `u32` buffer. The index counts elements of `x`, so the hit is at
+    /// `x_offset + index * x_inc`. On ties CLBlast returns one of the tied
+    /// indices, not necessarily the first. `n == 0` has no index and is
+    /// rejected with `InvalidDimension`, as CLBlast itself does.
+    pub fn $name(
+      queue: &Queue,
+      n: usize,
+      x_buffer: &Buffer<$elem>,
+      x_offset: usize,
+      x_inc: usize,
+    ) -> Result<usize, CLBlastError> {
+      if n == 0 {
+        return Err(CLBlastError::InvalidDimension);
+      }
+      check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+      let index = reduce_to_host::<u32>(queue, |out| {
+        crate::$routine(
+          queue,
+          n,
+          out,
+          Offset(0),
+          x_buffer,
+          Offset(x_offset),
+          x_inc,
+          &[],
+        )
+      })?;
+      Ok(index as usize)
+    }
+  };
+}
+
+index_reduction!(
+  /// Index of the element with the largest absolute value, validated.
+  isamax => i_samax,
+  f32
+);
+index_reduction!(
+  /// Index of the element with the largest absolute value (double), validated.
+  idamax => i_damax,
+  f64
+);
+index_reduction!(
+  /// Index of the element with the smallest absolute value, validated.
+  isamin => i_samin,
+  f32
+);
+index_reduction!(
+  /// Index of the element with the smallest absolute value (double), validated.
+  idamin => i_damin,
+  f64
+);
+index_reduction!(
+  /// Index of the largest element (a CLBlast extension), validated.
+  ismax => i_smax,
+  f32
+);
+index_reduction!(
+  /// Index of the largest element (double, a CLBlast extension), validated.
+  idmax => i_dmax,
+  f64
+);
+index_reduction!(
+  /// Index of the smallest element (a CLBlast extension), validated.
+  ismin => i_smin,
+  f32
+);
+index_reduction!(
+  /// Index of the smallest element (double, a CLBlast extension), validated.
+  idmin => i_dmin,
+  f64
+);
@@ -0,0 +1,398 @@
+//! Validated Level-2 overlays.
+//!
+//! Each function checks vector extents (`offset + (n - 1) * inc`) and the
+//! matrix footprint (`ld` and `offset`) against the buffer lengths, then
+//! forwards to the generated wrapper of the same name.
+//!
+//! Banded routines (`gbmv`, `sbmv`, `hbmv`) expect BLAS banded storage: with
+//! `band` stored diagonals, each column (`ColMajor`) or row (`RowMajor`) of
+//! the full matrix occupies `band` consecutive elements of `a`; for
+//! column-major `gbmv`, element `(i, j)` lives at `a[j * a_ld + ku + i - j]`,
+//! for row-major at `a[i * a_ld + kl + j - i]`.
+//! `a_ld` must be at least `band`, i.e. `kl + ku + 1` for `gbmv` and `k + 1`
+//! for `sbmv`/`hbmv`.
+//!
+//! Complex routines are generic over the buffer element type `T`; lengths
+//! are counted in elements of `T`, so use a two-component type such as
+//! `ocl::prm::Float2` (`c*`) or `ocl::prm::Double2` (`z*`).
+
+use ocl::{Buffer, OclPrm};
+
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose, CLBlastTriangle};
+use crate::error::CLBlastError;
+use crate::mem::{Element, precision};
+use crate::queue::AsQueue;
+use crate::validate::{check_matrix, check_vector};
+use crate::{ComplexF32, ComplexF64, CoreEvent, Ld, Offset};
+
+/// Shared checks for the `ger` family: `x` has `m` elements, `y` has `n`
+/// and `a` is an `m x n` matrix.
+#[allow(clippy::too_many_arguments)]
+fn check_ger<T: OclPrm>(
+  layout: CLBlastLayout,
+  m: usize,
+  n: usize,
+  x_buffer: &Buffer<T>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<T>,
+  y_offset: usize,
+  y_inc: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+) -> Result<(), CLBlastError> {
+  check_vector(x_buffer.len(), m, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  check_matrix(
+    a_buffer.len(),
+    layout,
+    m,
+    n,
+    a_offset,
+    a_ld,
+    CLBlastError::InvalidLeadDimA,
+  )
+}
+
+/// Rank-1 update `A = alpha * x * y^T + A`, validated.
+///
+/// `x` has `m` elements, `y` has `n` elements and `a` is `m x n`.
+#[allow(clippy::too_many_arguments)]
+pub fn sger<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  m: usize,
+  n: usize,
+  alpha: f32,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_ger(
+    layout, m, n, x_buffer, x_offset, x_inc, y_buffer, y_offset, y_inc, a_buffer, a_offset, a_ld,
+  )?;
+  Ok(crate::sger(
+    queue,
+    layout,
+    m,
+    n,
+    alpha,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    wait_for,
+  )?)
+}
+
+macro_rules! complex_ger {
+  ($(#[$doc:meta])* $name:ident, $scalar:ty, $elem_precision:ident) => {
+    $(#[$doc])*
+    #[allow(clippy::too_many_arguments)]
+    pub fn $name<'a, T: Element<Precision = precision::$elem_precision>>(
+      queue: &impl AsQueue,
+      layout: CLBlastLayout,
+      m: usize,
+      n: usize,
+      alpha: $scalar,
+      x_buffer: &Buffer<T>,
+      x_offset: usize,
+      x_inc: usize,
+      y_buffer: &Buffer<T>,
+      y_offset: usize,
+      y_inc: usize,
+      a_buffer: &Buffer<T>,
+      a_offset: usize,
+      a_ld: usize,
+      wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+    ) -> Result<Option<CoreEvent>, CLBlastError> {
+      check_ger(
+        layout, m, n, x_buffer, x_offset, x_inc, y_buffer, y_offset, y_inc, a_buffer, a_offset,
+        a_ld,
+      )?;
+      Ok(crate::$name(
+        queue,
+        layout,
+        m,
+        n,
+        alpha,
+        x_buffer,
+        Offset(x_offset),
+        x_inc,
+        y_buffer,
+        Offset(y_offset),
+        y_inc,
+        a_buffer,
+        Offset(a_offset),
+        Ld(a_ld),
+        wait_for,
+      )?)
+    }
+  };
+}
+
+complex_ger!(
+  /// Unconjugated rank-1 update `A = alpha * x * y^T + A` (single complex), validated.
+  ///
+  /// `y` is used as stored; see [`cgerc`] for the conjugated form.
+  cgeru,
+  ComplexF32,
+  ComplexSingle
+);
+complex_ger!(
+  /// Conjugated rank-1 update `A = alpha * x * y^H + A` (single complex), validated.
+  ///
+  /// Every element of `y` is conjugated before the product; see [`cgeru`]
+  /// for the unconjugated form.
+  cgerc,
+  ComplexF32,
+  ComplexSingle
+);
+complex_ger!(
+  /// Unconjugated rank-1 update `A = alpha * x * y^T + A` (double complex), validated.
+  ///
+  /// `y` is used as stored; see [`zgerc`] for the conjugated form.
+  zgeru,
+  ComplexF64,
+  ComplexDouble
+);
+complex_ger!(
+  /// Conjugated rank-1 update `A = alpha * x * y^H + A` (double complex), validated.
+  ///
+  /// Every element of `y` is conjugated before the product; see [`zgeru`]
+  /// for the unconjugated form.
+  zgerc,
+  ComplexF64,
+  ComplexDouble
+);
+
+/// Footprint check for banded storage: `count` rows (`RowMajor`) or columns
+/// (`ColMajor`) of `band` stored diagonals each, `a_ld >= band`.
+fn check_band(
+  len: usize,
+  layout: CLBlastLayout,
+  count: usize,
+  band: usize,
+  offset: usize,
+  ld: usize,
+) -> Result<(), CLBlastError> {
+  let (rows, cols) = match layout {
+    CLBlastLayout::RowMajor => (count, band),
+    CLBlastLayout::ColMajor => (band, count),
+  };
+  check_matrix(
+    len,
+    layout,
+    rows,
+    cols,
+    offset,
+    ld,
+    CLBlastError::InvalidLeadDimA,
+  )
+}
+
+/// General banded matrix-vector product `y = alpha * op(A) * x + beta * y`,
+/// validated.
+///
+/// `A` is `m x n` with `kl` sub- and `ku` super-diagonals in banded storage;
+/// `a_ld` must be at least `kl + ku + 1`. `x` has `n` elements (`m` when
+/// transposed) and `y` has `m` (`n` when transposed).
+#[allow(clippy::too_many_arguments)]
+pub fn sgbmv<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  kl: usize,
+  ku: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  beta: f32,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  let band = kl.saturating_add(ku).saturating_add(1);
+  let count = match layout {
+    CLBlastLayout::RowMajor => m,
+    CLBlastLayout::ColMajor => n,
+  };
+  check_band(a_buffer.len(), layout, count, band, a_offset, a_ld)?;
+  let (x_len, y_len) = match a_transpose {
+    CLBlastTranspose::No => (n, m),
+    CLBlastTranspose::Yes | CLBlastTranspose::Conjugate => (m, n),
+  };
+  check_vector(x_buffer.len(), x_len, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), y_len, y_offset, y_inc)?;
+  Ok(crate::sgbmv(
+    queue,
+    layout,
+    a_transpose,
+    m,
+    n,
+    kl,
+    ku,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    beta,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// Shared checks for `sbmv`/`hbmv`: `a` holds `n` rows/columns of `k + 1`
+/// stored diagonals, `x` and `y` have `n` elements.
+#[allow(clippy::too_many_arguments)]
+fn check_sbmv<T: OclPrm>(
+  layout: CLBlastLayout,
+  n: usize,
+  k: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  x_buffer: &Buffer<T>,
+  x_offset: usize,
+  x_inc: usize,
+  y_buffer: &Buffer<T>,
+  y_offset: usize,
+  y_inc: usize,
+) -> Result<(), CLBlastError> {
+  check_band(
+    a_buffer.len(),
+    layout,
+    n,
+    k.saturating_add(1),
+    a_offset,
+    a_ld,
+  )?;
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)
+}
+
+/// Symmetric banded matrix-vector product `y = alpha * A * x + beta * y`,
+/// validated.
+///
+/// `A` is `n x n` with `k` off-diagonals; only the triangle selected by
+/// `triangle` is stored, and `a_ld` must be at least `k + 1`. `x` and `y`
+/// must each hold `n` elements at their offsets and strides.
+#[allow(clippy::too_many_arguments)]
+pub fn ssbmv<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  beta: f32,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_sbmv(
+    layout, n, k, a_buffer, a_offset, a_ld, x_buffer, x_offset, x_inc, y_buffer, y_offset, y_inc,
+  )?;
+  Ok(crate::ssbmv(
+    queue,
+    layout,
+    triangle,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    beta,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// Hermitian banded matrix-vector product `y = alpha * A * x + beta * y`
+/// (single complex), validated.
+///
+/// `A` is `n x n` with `k` off-diagonals; only the triangle selected by
+/// `triangle` is stored, and `a_ld` must be at least `k + 1`. `x` and `y`
+/// must each hold `n` elements. The imaginary parts of the diagonal are
+/// assumed to be zero and are ignored (as in reference BLAS).
+#[allow(clippy::too_many_arguments)]
+pub fn chbmv<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  k: usize,
+  alpha: ComplexF32,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  x_buffer: &Buffer<T>,
+  x_offset: usize,
+  x_inc: usize,
+  beta: ComplexF32,
+  y_buffer: &Buffer<T>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_sbmv(
+    layout, n, k, a_buffer, a_offset, a_ld, x_buffer, x_offset, x_inc, y_buffer, y_offset, y_inc,
+  )?;
+  Ok(crate::chbmv(
+    queue,
+    layout,
+    triangle,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    beta,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
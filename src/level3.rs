@@ -0,0 +1,2259 @@
+//! Validated Level-3 overlays.
+//!
+//! Each function checks every matrix footprint (`ld` and `offset`) against
+//! its buffer length, then forwards to the generated wrapper of the same
+//! name.
+//!
+//! Sub-buffers (`Buffer::create_sub_buffer`) are passed as their own
+//! `cl_mem`, so offsets and lengths are relative to the sub-buffer; do not
+//! add its origin to `*_offset`.
+//!
+//! Dimensions and leading dimensions above [`MAX_DIMENSION`]
+//! (`i32::MAX`) are rejected with `DimensionTooLarge`: CLBlast's kernels
+//! index with 32-bit integers and would silently wrap.
+//!
+//! Degenerate sizes are decided here rather than in CLBlast: an empty
+//! output (`m == 0` or `n == 0`) enqueues nothing and returns `Ok(None)`.
+//! For `gemm`, `syrk`, `herk` and `her2k`, `k == 0` follows the BLAS
+//! convention `C = beta * C` (on the stored triangle for the rank updates):
+//! nothing is enqueued for `beta == 1`, `c` is zero-filled for `beta == 0`
+//! (so NaN/Inf in it do not survive) and scaled with `scal` otherwise.
+//!
+//! With the `alias-check` feature, the triangular routines `trmm` and `trsm`
+//! also reject `a_buffer` and `b_buffer` being the same buffer: `b` is
+//! overwritten in place while `a` is still being read.
+//!
+//! Complex routines are generic over the buffer element type `T`; lengths
+//! are counted in elements of `T`, so use a two-component type such as
+//! `num_complex::Complex<f32>` or `ocl::prm::Float2` (`c*`), and their
+//! `f64` counterparts (`z*`). Complex scalars are [`ComplexF32`] and
+//! [`ComplexF64`], i.e. `num_complex` types with the `complex` feature.
+//!
+//! Scalars follow BLAS, not the element type: `herk` takes a real `alpha`
+//! and `beta` (`f32`/`f64`), `her2k` a complex `alpha` with a real `beta`,
+//! and `hemm` complex ones for both.
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt;
+
+use ocl::{Buffer, OclPrm, Queue};
+
+use crate::clblast_sys::{
+  CLBlastDiagonal, CLBlastLayout, CLBlastPrecision, CLBlastSide, CLBlastStatusCode,
+  CLBlastTranspose, CLBlastTriangle, cl_device_id,
+};
+use crate::complex::{complex_f32, complex_f64};
+use crate::error::CLBlastError;
+use crate::mem::{AsMem, Element, precision};
+use crate::queue::AsQueue;
+use crate::status::check;
+use crate::validate::{check_dims, check_distinct, check_matrix};
+use crate::{ComplexF32, ComplexF64, CoreEvent, Ld, Offset};
+
+pub use crate::validate::MAX_DIMENSION;
+
+/// Shape and storage of one `gemm` call, for logging.
+///
+/// Displays as e.g. `RowMajor N×T 128x256x512 (ld 512/256/256)`: the layout,
+/// `op(A)`/`op(B)` (`N`, `T` or `C`), `m x n x k`, then `a_ld/b_ld/c_ld`.
+/// With the `tracing` feature, every `gemm` overlay (and the builder front
+/// ends going through them) logs it at `warn` level when validation fails.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GemmShape {
+  /// Storage order of all three matrices.
+  pub layout: CLBlastLayout,
+  /// `op(A)`.
+  pub a_transpose: CLBlastTranspose,
+  /// `op(B)`.
+  pub b_transpose: CLBlastTranspose,
+  /// Rows of `op(A)` and `C`.
+  pub m: usize,
+  /// Columns of `op(B)` and `C`.
+  pub n: usize,
+  /// Inner dimension.
+  pub k: usize,
+  /// Leading dimension of `A`.
+  pub a_ld: usize,
+  /// Leading dimension of `B`.
+  pub b_ld: usize,
+  /// Leading dimension of `C`.
+  pub c_ld: usize,
+}
+
+impl fmt::Display for GemmShape {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let op = |trans| match trans {
+      CLBlastTranspose::No => 'N',
+      CLBlastTranspose::Yes => 'T',
+      CLBlastTranspose::Conjugate => 'C',
+    };
+    write!(
+      f,
+      "{:?} {}×{} {}x{}x{} (ld {}/{}/{})",
+      self.layout,
+      op(self.a_transpose),
+      op(self.b_transpose),
+      self.m,
+      self.n,
+      self.k,
+      self.a_ld,
+      self.b_ld,
+      self.c_ld
+    )
+  }
+}
+
+/// Shared checks for `gemm`: `op(A)` is `m x k`, `op(B)` is `k x n` and `c`
+/// is `m x n`.
+#[allow(clippy::too_many_arguments)]
+fn check_gemm<T: OclPrm>(
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+) -> Result<(), CLBlastError> {
+  let checked = (|| -> Result<(), CLBlastError> {
+    check_dims(&[m, n, k, a_ld, b_ld, c_ld])?;
+    let stored = |trans: CLBlastTranspose, rows: usize, cols: usize| match trans {
+      CLBlastTranspose::No => (rows, cols),
+      CLBlastTranspose::Yes | CLBlastTranspose::Conjugate => (cols, rows),
+    };
+    let (a_rows, a_cols) = stored(a_transpose, m, k);
+    let (b_rows, b_cols) = stored(b_transpose, k, n);
+    check_matrix(
+      a_buffer.len(),
+      layout,
+      a_rows,
+      a_cols,
+      a_offset,
+      a_ld,
+      CLBlastError::InvalidLeadDimA,
+    )?;
+    check_matrix(
+      b_buffer.len(),
+      layout,
+      b_rows,
+      b_cols,
+      b_offset,
+      b_ld,
+      CLBlastError::InvalidLeadDimB,
+    )?;
+    check_matrix(
+      c_buffer.len(),
+      layout,
+      m,
+      n,
+      c_offset,
+      c_ld,
+      CLBlastError::InvalidLeadDimC,
+    )
+  })();
+  if let Err(error) = &checked {
+    let shape = GemmShape {
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      a_ld,
+      b_ld,
+      c_ld,
+    };
+    #[cfg(feature = "tracing")]
+    tracing::warn!(%shape, %error, "clblast: gemm rejected");
+    #[cfg(not(feature = "tracing"))]
+    let _ = (shape, error);
+  }
+  checked
+}
+
+/// `C = beta * C` for an `m x n` matrix at `c_offset`, one `scal(len,
+/// offset, wait_for)` per stored row (or column) unless the matrix is
+/// contiguous. Returns the last event.
+fn scale_matrix<'a>(
+  layout: CLBlastLayout,
+  m: usize,
+  n: usize,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  scal: impl Fn(usize, usize, &[&CoreEvent]) -> crate::Result<Option<CoreEvent>>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  let (outer, inner) = match layout {
+    CLBlastLayout::RowMajor => (m, n),
+    CLBlastLayout::ColMajor => (n, m),
+  };
+  let wait_for: Vec<&CoreEvent> = wait_for.into_iter().collect();
+  if c_ld == inner {
+    return Ok(scal(outer * inner, c_offset, &wait_for)?);
+  }
+  let mut last: Option<CoreEvent> = None;
+  for i in 0..outer {
+    let previous;
+    let wait: &[&CoreEvent] = match &last {
+      Some(event) => {
+        previous = [event];
+        &previous
+      }
+      None => &wait_for,
+    };
+    last = scal(inner, c_offset + i * c_ld, wait)?;
+  }
+  Ok(last)
+}
+
+/// `C = beta * C` for the stored triangle of an `n x n` matrix: `scal` runs
+/// once per row (or column) over its part of the triangle.
+fn scale_triangle<'a>(
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+  scal: impl Fn(usize, usize, &[&CoreEvent]) -> crate::Result<Option<CoreEvent>>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  // Whether line `i` holds elements `i..n` (rather than `0..=i`).
+  let from_diagonal = matches!(
+    (layout, triangle),
+    (CLBlastLayout::RowMajor, CLBlastTriangle::Upper)
+      | (CLBlastLayout::ColMajor, CLBlastTriangle::Lower)
+  );
+  let wait_for: Vec<&CoreEvent> = wait_for.into_iter().collect();
+  let mut last: Option<CoreEvent> = None;
+  for i in 0..n {
+    let previous;
+    let wait: &[&CoreEvent] = match &last {
+      Some(event) => {
+        previous = [event];
+        &previous
+      }
+      None => &wait_for,
+    };
+    last = if from_diagonal {
+      scal(n - i, c_offset + i * c_ld + i, wait)?
+    } else {
+      scal(i + 1, c_offset + i * c_ld, wait)?
+    };
+  }
+  Ok(last)
+}
+
+/// Set `len` elements of `buffer` from `offset` to zero, after `wait_for`.
+///
+/// Stands in for `scal` by a zero `beta`: BLAS then overwrites `C` rather
+/// than scaling it, so NaN/Inf already in `C` must not survive.
+fn zero_fill<'a, T: OclPrm>(
+  queue: &impl AsQueue,
+  buffer: &Buffer<T>,
+  offset: usize,
+  len: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  let queue = &crate::queue::in_order_queue(queue)?;
+  let _marker = crate::enqueue_marker_wait(queue, wait_for)?;
+  let zero = T::default();
+  let size = std::mem::size_of::<T>();
+  let mut raw_ev: cl_sys::cl_event = std::ptr::null_mut();
+  let err = unsafe {
+    cl_sys::clEnqueueFillBuffer(
+      queue.as_raw_queue(),
+      buffer.as_raw_mem(),
+      &zero as *const T as *const std::ffi::c_void,
+      size,
+      offset * size,
+      len * size,
+      0,
+      std::ptr::null(),
+      &mut raw_ev,
+    )
+  };
+  if err != cl_sys::CL_SUCCESS {
+    return Err(CLBlastError::OpenCL(err));
+  }
+  unsafe { crate::completion_event(queue, raw_ev as crate::clblast_sys::cl_event) }
+}
+
+/// General matrix multiply `C = alpha * op(A) * op(B) + beta * C`, validated.
+///
+/// `op(A)` is `m x k`, `op(B)` is `k x n` and `c` is `m x n`. Returns
+/// `Ok(None)` without enqueueing anything when `m == 0` or `n == 0`. When
+/// `k == 0` the product is empty and `c` is only scaled by `beta` (nothing
+/// is enqueued if `beta == 1`, and `c` is zero-filled if `beta == 0`).
+///
+/// `c` is read as well as written: with `beta != 0` the result accumulates
+/// into its current contents. CLBlast may also read `c` when `beta == 0`,
+/// so it should not hold NaN/Inf garbage. If `c` was filled by a
+/// non-blocking write, pass that write's event in `wait_for`; the kernel
+/// otherwise only orders after it on an in-order queue.
+///
+/// The returned event is the one CLBlast reports for the routine, i.e. the
+/// last command it enqueued (or a marker right behind it if CLBlast reports
+/// none), and completes only once `c` holds the result. It is never the
+/// marker used to honour `wait_for` (that one is released before
+/// returning), so it can be passed straight to a dependent call's
+/// `wait_for`. When `k == 0` it is the event of the final `beta` scaling.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_gemm(
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_matrix(
+      layout,
+      m,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::sscal(
+            queue,
+            len,
+            beta,
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::sgemm(
+    queue,
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Double-precision [`sgemm`], validated the same way.
+///
+/// With the `precision-check` feature, the queue's device is first checked
+/// for fp64 support and `NoDoublePrecision` is returned instead of the
+/// opaque failure CLBlast reports on devices without it. The check reads
+/// the device's extension string on every call, hence opt-in.
+#[allow(clippy::too_many_arguments)]
+pub fn dgemm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: f64,
+  a_buffer: &Buffer<f64>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f64>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f64,
+  c_buffer: &Buffer<f64>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_gemm(
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  #[cfg(feature = "precision-check")]
+  crate::caps::require_precision(
+    &crate::queue::queue_device(queue)?,
+    crate::clblast_sys::CLBlastPrecision::Double,
+  )?;
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_matrix(
+      layout,
+      m,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::dscal(
+            queue,
+            len,
+            beta,
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::dgemm(
+    queue,
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Single-complex [`sgemm`], validated the same way. `k == 0` scales `c` by
+/// `beta` with `cscal`, or zero-fills it for a zero `beta`.
+#[allow(clippy::too_many_arguments)]
+pub fn cgemm<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: ComplexF32,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: ComplexF32,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_gemm(
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if crate::complex::parts_f32(beta) == [1.0, 0.0] {
+      return Ok(None);
+    }
+    return scale_matrix(
+      layout,
+      m,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if crate::complex::parts_f32(beta) == [0.0, 0.0] {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::cscal(
+            queue,
+            len,
+            beta,
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::cgemm(
+    queue,
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Double-complex [`sgemm`], validated the same way. See [`cgemm`] and,
+/// for the `precision-check` feature, [`dgemm`].
+#[allow(clippy::too_many_arguments)]
+pub fn zgemm<'a, T: Element<Precision = precision::ComplexDouble>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: ComplexF64,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: ComplexF64,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_gemm(
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  #[cfg(feature = "precision-check")]
+  crate::caps::require_precision(
+    &crate::queue::queue_device(queue)?,
+    crate::clblast_sys::CLBlastPrecision::ComplexDouble,
+  )?;
+  if k == 0 {
+    if crate::complex::parts_f64(beta) == [1.0, 0.0] {
+      return Ok(None);
+    }
+    return scale_matrix(
+      layout,
+      m,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if crate::complex::parts_f64(beta) == [0.0, 0.0] {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::zscal(
+            queue,
+            len,
+            beta,
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::zgemm(
+    queue,
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Scratch bytes [`sgemm_with_temp_buffer`] needs for this problem on the
+/// device of `queue`. May be `0` when CLBlast runs it without scratch.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm_temp_buffer_size(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  a_offset: usize,
+  a_ld: usize,
+  b_offset: usize,
+  b_ld: usize,
+  c_offset: usize,
+  c_ld: usize,
+) -> Result<usize, CLBlastError> {
+  let mut size = 0usize;
+  let status = crate::with_queue_ptr(queue, |qptr| unsafe {
+    crate::clblast_sys::CLBlastSGemmTempBufferSize(
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      a_offset,
+      a_ld,
+      b_offset,
+      b_ld,
+      c_offset,
+      c_ld,
+      qptr,
+      &mut size,
+    )
+  });
+  check(status)?;
+  Ok(size)
+}
+
+/// [`sgemm`] with caller-provided scratch memory, validated the same way.
+///
+/// If `temp_buffer` is too small CLBlast enqueues nothing and this returns
+/// [`CLBlastError::TempBufferTooSmall`] with the size from
+/// [`sgemm_temp_buffer_size`], so the caller can grow the buffer and retry.
+/// Empty outputs and `k == 0` never touch `temp_buffer`.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm_with_temp_buffer<'a, T: OclPrm>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  c_offset: usize,
+  c_ld: usize,
+  temp_buffer: &Buffer<T>,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  if m == 0 || n == 0 || k == 0 {
+    return sgemm(
+      queue,
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      alpha,
+      a_buffer,
+      a_offset,
+      a_ld,
+      b_buffer,
+      b_offset,
+      b_ld,
+      beta,
+      c_buffer,
+      c_offset,
+      c_ld,
+      wait_for,
+    );
+  }
+  check_gemm(
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  let queue = &crate::queue::in_order_queue(queue)?;
+  let _marker = crate::enqueue_marker_wait(queue, wait_for)?;
+  let mut raw_ev: crate::clblast_sys::cl_event = std::ptr::null_mut();
+  let status = crate::with_queue_ptr(queue, |qptr| unsafe {
+    crate::clblast_sys::CLBlastSgemmWithTempBuffer(
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      alpha,
+      crate::to_mem(a_buffer),
+      a_offset,
+      a_ld,
+      crate::to_mem(b_buffer),
+      b_offset,
+      b_ld,
+      beta,
+      crate::to_mem(c_buffer),
+      c_offset,
+      c_ld,
+      qptr,
+      &mut raw_ev,
+      crate::to_mem(temp_buffer),
+    )
+  });
+  if status == CLBlastStatusCode::InsufficientMemoryTemp {
+    let needed = sgemm_temp_buffer_size(
+      queue,
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      a_offset,
+      a_ld,
+      b_offset,
+      b_ld,
+      c_offset,
+      c_ld,
+    )?;
+    return Err(CLBlastError::TempBufferTooSmall { needed });
+  }
+  check(status)?;
+  unsafe { crate::completion_event(queue, raw_ev) }
+}
+
+/// Device, precision, shape and offsets of a `gemm` whose scratch size has
+/// been queried.
+type TempSizeKey = (cl_device_id, CLBlastPrecision, GemmShape, [usize; 3]);
+
+thread_local! {
+  /// Scratch sizes from `*GemmTempBufferSize`, which depend only on the key.
+  static TEMP_SIZES: RefCell<HashMap<TempSizeKey, usize>> = RefCell::new(HashMap::new());
+  /// Cache misses of `TEMP_SIZES`, i.e. sizes actually queried from CLBlast.
+  static TEMP_SIZE_QUERIES: Cell<usize> = const { Cell::new(0) };
+}
+
+/// How many scratch sizes [`sgemm_with_temp`] has queried from CLBlast on
+/// this thread; calls answered from its cache do not count.
+pub fn temp_size_queries() -> usize {
+  TEMP_SIZE_QUERIES.with(Cell::get)
+}
+
+/// [`sgemm_with_temp_buffer`] that sizes the scratch itself.
+///
+/// With `temp_buffer: None` a `Buffer<u8>` of [`sgemm_temp_buffer_size`]
+/// bytes is allocated on `queue` for this call; the size is cached per
+/// device, shape and offsets (on this thread), so repeated identical calls
+/// query CLBlast once. Pass `Some` to reuse a buffer across calls.
+///
+/// The scratch must stay untouched until the returned event completes.
+/// The allocated one is dropped on return, which is fine since OpenCL keeps
+/// a buffer alive until the commands using it finish; a caller-provided one
+/// must not be written, or shared with a concurrent `gemm` on another
+/// queue, before then.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm_with_temp<'a>(
+  queue: &Queue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  c_offset: usize,
+  c_ld: usize,
+  temp_buffer: Option<&Buffer<u8>>,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  let run = |temp: &Buffer<u8>| {
+    sgemm_with_temp_buffer(
+      queue,
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      alpha,
+      a_buffer,
+      a_offset,
+      a_ld,
+      b_buffer,
+      b_offset,
+      b_ld,
+      beta,
+      c_buffer,
+      c_offset,
+      c_ld,
+      temp,
+      wait_for,
+    )
+  };
+  if let Some(temp) = temp_buffer {
+    return run(temp);
+  }
+  let shape = GemmShape {
+    layout,
+    a_transpose,
+    b_transpose,
+    m,
+    n,
+    k,
+    a_ld,
+    b_ld,
+    c_ld,
+  };
+  let key = (
+    queue.device().as_raw() as cl_device_id,
+    CLBlastPrecision::Single,
+    shape,
+    [a_offset, b_offset, c_offset],
+  );
+  let size = match TEMP_SIZES.with(|sizes| sizes.borrow().get(&key).copied()) {
+    Some(size) => size,
+    None => {
+      let size = sgemm_temp_buffer_size(
+        queue,
+        layout,
+        a_transpose,
+        b_transpose,
+        m,
+        n,
+        k,
+        a_offset,
+        a_ld,
+        b_offset,
+        b_ld,
+        c_offset,
+        c_ld,
+      )?;
+      TEMP_SIZES.with(|sizes| sizes.borrow_mut().insert(key, size));
+      TEMP_SIZE_QUERIES.with(|queries| queries.set(queries.get() + 1));
+      size
+    }
+  };
+  // OpenCL has no empty buffers.
+  let temp = Buffer::<u8>::builder()
+    .queue(queue.clone())
+    .len(size.max(1))
+    .build()?;
+  run(&temp)
+}
+
+/// Offset of the last of `batch_count` matrices spaced `stride` apart.
+#[cfg(feature = "batched")]
+fn last_batch_offset(
+  offset: usize,
+  stride: usize,
+  batch_count: usize,
+) -> Result<usize, CLBlastError> {
+  stride
+    .checked_mul(batch_count - 1)
+    .and_then(|skip| skip.checked_add(offset))
+    .ok_or(CLBlastError::InvalidDimension)
+}
+
+/// Shared checks for the strided-batched `gemm`s. The first batch entry is
+/// checked like a single `gemm`; if it fits but the last one does not, the
+/// strides walk off the end of a buffer, which is `InvalidDimension`.
+#[cfg(feature = "batched")]
+#[allow(clippy::too_many_arguments)]
+fn check_gemm_strided_batched<T: OclPrm>(
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  a_stride: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  b_stride: usize,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  c_stride: usize,
+  batch_count: usize,
+) -> Result<(), CLBlastError> {
+  if batch_count == 0 {
+    return Err(CLBlastError::InvalidBatchCount);
+  }
+  check_dims(&[a_stride, b_stride, c_stride, batch_count])?;
+  let check = |a_offset, b_offset, c_offset| {
+    check_gemm(
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      a_buffer,
+      a_offset,
+      a_ld,
+      b_buffer,
+      b_offset,
+      b_ld,
+      c_buffer,
+      c_offset,
+      c_ld,
+    )
+  };
+  check(a_offset, b_offset, c_offset)?;
+  check(
+    last_batch_offset(a_offset, a_stride, batch_count)?,
+    last_batch_offset(b_offset, b_stride, batch_count)?,
+    last_batch_offset(c_offset, c_stride, batch_count)?,
+  )
+  .map_err(|_| CLBlastError::InvalidDimension)
+}
+
+macro_rules! gemm_strided_batched {
+  (
+    $(#[$doc:meta])*
+    $name:ident $(<$t:ident: $elem_precision:ident>)?, $elem:ty, $scalar:ty $(, $precision:ident)?
+  ) => {
+    $(#[$doc])*
+    #[cfg(feature = "batched")]
+    #[allow(clippy::too_many_arguments)]
+    pub fn $name<'a $(, $t: Element<Precision = precision::$elem_precision>)?>(
+      queue: &impl AsQueue,
+      layout: CLBlastLayout,
+      a_transpose: CLBlastTranspose,
+      b_transpose: CLBlastTranspose,
+      m: usize,
+      n: usize,
+      k: usize,
+      alpha: $scalar,
+      a_buffer: &Buffer<$elem>,
+      a_offset: usize,
+      a_ld: usize,
+      a_stride: usize,
+      b_buffer: &Buffer<$elem>,
+      b_offset: usize,
+      b_ld: usize,
+      b_stride: usize,
+      beta: $scalar,
+      c_buffer: &Buffer<$elem>,
+      c_offset: usize,
+      c_ld: usize,
+      c_stride: usize,
+      batch_count: usize,
+      wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+    ) -> Result<Option<CoreEvent>, CLBlastError> {
+      check_gemm_strided_batched(
+        layout,
+        a_transpose,
+        b_transpose,
+        m,
+        n,
+        k,
+        a_buffer,
+        a_offset,
+        a_ld,
+        a_stride,
+        b_buffer,
+        b_offset,
+        b_ld,
+        b_stride,
+        c_buffer,
+        c_offset,
+        c_ld,
+        c_stride,
+        batch_count,
+      )?;
+      if m == 0 || n == 0 {
+        return Ok(None);
+      }
+      $(
+        #[cfg(feature = "precision-check")]
+        crate::caps::require_precision(
+          &crate::queue::queue_device(queue)?,
+          crate::clblast_sys::CLBlastPrecision::$precision,
+        )?;
+      )?
+      Ok(crate::$name(
+        queue,
+        layout,
+        a_transpose,
+        b_transpose,
+        m,
+        n,
+        k,
+        alpha,
+        a_buffer,
+        Offset(a_offset),
+        Ld(a_ld),
+        a_stride,
+        b_buffer,
+        Offset(b_offset),
+        Ld(b_ld),
+        b_stride,
+        beta,
+        c_buffer,
+        Offset(c_offset),
+        Ld(c_ld),
+        c_stride,
+        batch_count,
+        wait_for,
+      )?)
+    }
+  };
+}
+
+gemm_strided_batched!(
+  /// `batch_count` independent `sgemm`s in one launch, validated. Matrix `i`
+  /// of each operand starts at `*_offset + i * *_stride` (in elements);
+  /// shape, leading dimensions and scalars are shared.
+  ///
+  /// The first batch entry is checked like [`sgemm`]; a later entry running
+  /// past the end of its buffer (a stride too large for it) is rejected with
+  /// `InvalidDimension`, as the device would otherwise read out of bounds.
+  /// `batch_count == 0` is rejected with `InvalidBatchCount`; an empty
+  /// output (`m == 0` or `n == 0`) enqueues nothing and returns `Ok(None)`.
+  ///
+  /// As with [`sgemm`], the returned event is CLBlast's completion event for
+  /// the whole batch, so a dependent kernel can pass it to `ewait` (or any
+  /// `wait_for`) instead of finishing the queue.
+  sgemm_strided_batched,
+  f32,
+  f32
+);
+gemm_strided_batched!(
+  /// Double-precision [`sgemm_strided_batched`], validated the same way
+  /// (and, with `precision-check`, checked for fp64 support like [`dgemm`]).
+  dgemm_strided_batched,
+  f64,
+  f64,
+  Double
+);
+gemm_strided_batched!(
+  /// Single-complex [`sgemm_strided_batched`], validated the same way.
+  cgemm_strided_batched<T: ComplexSingle>,
+  T,
+  ComplexF32
+);
+gemm_strided_batched!(
+  /// Double-complex [`sgemm_strided_batched`], validated the same way.
+  zgemm_strided_batched<T: ComplexDouble>,
+  T,
+  ComplexF64,
+  ComplexDouble
+);
+
+/// `batch_count` independent `sgemm`s in one launch with per-batch offsets
+/// and scalars, validated. Matrix `i` of each operand starts at
+/// `*_offsets[i]` in its buffer and is scaled by `alphas[i]`/`betas[i]`;
+/// shape and leading dimensions are shared.
+///
+/// All five slices must hold exactly `batch_count` entries, otherwise
+/// `InvalidBatchCount` is returned before CLBlast sees their pointers
+/// (as is `batch_count == 0`). Every batch entry is checked against its
+/// buffer. An empty output (`m == 0` or `n == 0`) enqueues nothing and
+/// returns `Ok(None)`; otherwise the returned event completes with the
+/// whole batch. Prefer [`sgemm_strided_batched`] when the matrices are
+/// evenly spaced and share their scalars.
+#[cfg(feature = "batched")]
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm_batched<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alphas: &[f32],
+  a_buffer: &Buffer<f32>,
+  a_offsets: &[usize],
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offsets: &[usize],
+  b_ld: usize,
+  betas: &[f32],
+  c_buffer: &Buffer<f32>,
+  c_offsets: &[usize],
+  c_ld: usize,
+  batch_count: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  let lens = [
+    alphas.len(),
+    a_offsets.len(),
+    b_offsets.len(),
+    betas.len(),
+    c_offsets.len(),
+  ];
+  if batch_count == 0 || lens.iter().any(|&len| len != batch_count) {
+    return Err(CLBlastError::InvalidBatchCount);
+  }
+  check_dims(&[batch_count])?;
+  for ((&a_offset, &b_offset), &c_offset) in a_offsets.iter().zip(b_offsets).zip(c_offsets) {
+    check_gemm(
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      a_buffer,
+      a_offset,
+      a_ld,
+      b_buffer,
+      b_offset,
+      b_ld,
+      c_buffer,
+      c_offset,
+      c_ld,
+    )?;
+  }
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  // Safety: the slices outlive the call, and CLBlast reads `batch_count`
+  // entries from each, which the length check above guarantees exist.
+  Ok(unsafe {
+    crate::sgemm_batched(
+      queue,
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      alphas.as_ptr(),
+      a_buffer,
+      a_offsets.as_ptr(),
+      Ld(a_ld),
+      b_buffer,
+      b_offsets.as_ptr(),
+      Ld(b_ld),
+      betas.as_ptr(),
+      c_buffer,
+      c_offsets.as_ptr(),
+      Ld(c_ld),
+      batch_count,
+      wait_for,
+    )
+  }?)
+}
+
+/// `sgemm` over matrices living in separate allocations, validated.
+///
+/// CLBlast's batched routines only address one buffer per operand (with
+/// per-batch offsets) and cannot gather from several allocations, so this
+/// is a convenience loop issuing one [`sgemm`] per batch entry, all with the
+/// same shape, leading dimensions and scalars. Every call waits on
+/// `wait_for`; the returned events are in batch order. The three buffer
+/// slices must be non-empty and of equal length, otherwise
+/// `InvalidBatchCount` is returned before anything is enqueued.
+#[allow(clippy::too_many_arguments)]
+pub fn sgemm_batched_separate<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  b_transpose: CLBlastTranspose,
+  m: usize,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffers: &[&Buffer<f32>],
+  a_ld: usize,
+  b_buffers: &[&Buffer<f32>],
+  b_ld: usize,
+  beta: f32,
+  c_buffers: &[&Buffer<f32>],
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Vec<Option<CoreEvent>>, CLBlastError> {
+  let count = a_buffers.len();
+  if count == 0 || b_buffers.len() != count || c_buffers.len() != count {
+    return Err(CLBlastError::InvalidBatchCount);
+  }
+  for ((a, b), c) in a_buffers.iter().zip(b_buffers).zip(c_buffers) {
+    check_gemm(
+      layout,
+      a_transpose,
+      b_transpose,
+      m,
+      n,
+      k,
+      *a,
+      0,
+      a_ld,
+      *b,
+      0,
+      b_ld,
+      *c,
+      0,
+      c_ld,
+    )?;
+  }
+  let wait_for: Vec<&CoreEvent> = wait_for.into_iter().collect();
+  a_buffers
+    .iter()
+    .zip(b_buffers)
+    .zip(c_buffers)
+    .map(|((a, b), c)| {
+      sgemm(
+        queue,
+        layout,
+        a_transpose,
+        b_transpose,
+        m,
+        n,
+        k,
+        alpha,
+        a,
+        0,
+        a_ld,
+        b,
+        0,
+        b_ld,
+        beta,
+        c,
+        0,
+        c_ld,
+        wait_for.iter().copied(),
+      )
+    })
+    .collect()
+}
+
+/// Shared checks for `symm`/`hemm`: `a` is square (`m x m` for
+/// `Side::Left`, `n x n` for `Side::Right`), `b` and `c` are `m x n`.
+#[allow(clippy::too_many_arguments)]
+fn check_symm<T: OclPrm>(
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  m: usize,
+  n: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+) -> Result<(), CLBlastError> {
+  check_dims(&[m, n, a_ld, b_ld, c_ld])?;
+  let ka = match side {
+    CLBlastSide::Left => m,
+    CLBlastSide::Right => n,
+  };
+  check_matrix(
+    a_buffer.len(),
+    layout,
+    ka,
+    ka,
+    a_offset,
+    a_ld,
+    CLBlastError::InvalidLeadDimA,
+  )?;
+  check_matrix(
+    b_buffer.len(),
+    layout,
+    m,
+    n,
+    b_offset,
+    b_ld,
+    CLBlastError::InvalidLeadDimB,
+  )?;
+  check_matrix(
+    c_buffer.len(),
+    layout,
+    m,
+    n,
+    c_offset,
+    c_ld,
+    CLBlastError::InvalidLeadDimC,
+  )
+}
+
+/// Symmetric multiply, validated: `C = alpha * A * B + beta * C` for
+/// `Side::Left`, `C = alpha * B * A + beta * C` for `Side::Right`.
+///
+/// `a` must hold a square `m x m` (left) or `n x n` (right) matrix. Only the
+/// triangle selected by `triangle` is read; the other one may contain
+/// anything.
+#[allow(clippy::too_many_arguments)]
+pub fn ssymm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  triangle: CLBlastTriangle,
+  m: usize,
+  n: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_symm(
+    layout, side, m, n, a_buffer, a_offset, a_ld, b_buffer, b_offset, b_ld, c_buffer, c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  Ok(crate::ssymm(
+    queue,
+    layout,
+    side,
+    triangle,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Hermitian multiply (single complex), validated: `C = alpha * A * B + beta * C`
+/// for `Side::Left`, `C = alpha * B * A + beta * C` for `Side::Right`.
+///
+/// `a` must hold a square `m x m` (left) or `n x n` (right) matrix. Only the
+/// triangle selected by `triangle` is read; the other one is implied by
+/// conjugate symmetry and the imaginary parts of the diagonal are ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn chemm<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  triangle: CLBlastTriangle,
+  m: usize,
+  n: usize,
+  alpha: ComplexF32,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: ComplexF32,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_symm(
+    layout, side, m, n, a_buffer, a_offset, a_ld, b_buffer, b_offset, b_ld, c_buffer, c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  Ok(crate::chemm(
+    queue,
+    layout,
+    side,
+    triangle,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Hermitian multiply (double complex), validated. See [`chemm`].
+#[allow(clippy::too_many_arguments)]
+pub fn zhemm<'a, T: Element<Precision = precision::ComplexDouble>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  triangle: CLBlastTriangle,
+  m: usize,
+  n: usize,
+  alpha: ComplexF64,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: ComplexF64,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_symm(
+    layout, side, m, n, a_buffer, a_offset, a_ld, b_buffer, b_offset, b_ld, c_buffer, c_offset,
+    c_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  Ok(crate::zhemm(
+    queue,
+    layout,
+    side,
+    triangle,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Shared checks for `syrk`/`herk`: `a` is `n x k` (`Transpose::No`) or
+/// `k x n` (otherwise) and `c` is `n x n`.
+#[allow(clippy::too_many_arguments)]
+fn check_syrk<T: OclPrm>(
+  layout: CLBlastLayout,
+  a_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+) -> Result<(), CLBlastError> {
+  check_dims(&[n, k, a_ld, c_ld])?;
+  let (a_rows, a_cols) = match a_transpose {
+    CLBlastTranspose::No => (n, k),
+    CLBlastTranspose::Yes | CLBlastTranspose::Conjugate => (k, n),
+  };
+  check_matrix(
+    a_buffer.len(),
+    layout,
+    a_rows,
+    a_cols,
+    a_offset,
+    a_ld,
+    CLBlastError::InvalidLeadDimA,
+  )?;
+  check_matrix(
+    c_buffer.len(),
+    layout,
+    n,
+    n,
+    c_offset,
+    c_ld,
+    CLBlastError::InvalidLeadDimC,
+  )
+}
+
+/// Symmetric rank-k update, validated: `C = alpha * A * A^T + beta * C`
+/// (`Transpose::No`, `a` is `n x k`) or `C = alpha * A^T * A + beta * C`
+/// (`Transpose::Yes`, `a` is `k x n`).
+///
+/// `c` must hold an `n x n` matrix with `c_ld >= n`. Only the triangle
+/// selected by `triangle` is read and written; the other one is left as is.
+/// With `k == 0` that triangle is only scaled by `beta` (see the module
+/// docs).
+#[allow(clippy::too_many_arguments)]
+pub fn ssyrk<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  a_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<f32>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_syrk(
+    layout,
+    a_transpose,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_triangle(
+      layout,
+      triangle,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::sscal(
+            queue,
+            len,
+            beta,
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::ssyrk(
+    queue,
+    layout,
+    triangle,
+    a_transpose,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Hermitian rank-k update (single complex), validated:
+/// `C = alpha * A * A^H + beta * C` (`Transpose::No`, `a` is `n x k`) or
+/// `C = alpha * A^H * A + beta * C` (`Transpose::Conjugate`, `a` is `k x n`).
+///
+/// `alpha` and `beta` are real. `c` must hold an `n x n` matrix with
+/// `c_ld >= n`. Only the triangle selected by `triangle` is read and
+/// written; the other one is left as is. With `k == 0` that triangle is
+/// only scaled by `beta`, imaginary parts of the diagonal included.
+#[allow(clippy::too_many_arguments)]
+pub fn cherk<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  a_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  alpha: f32,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_syrk(
+    layout,
+    a_transpose,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_triangle(
+      layout,
+      triangle,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::cscal(
+            queue,
+            len,
+            complex_f32(beta, 0.0),
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::cherk(
+    queue,
+    layout,
+    triangle,
+    a_transpose,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Hermitian rank-k update (double complex), validated. See [`cherk`];
+/// `alpha` and `beta` are real here too.
+#[allow(clippy::too_many_arguments)]
+pub fn zherk<'a, T: Element<Precision = precision::ComplexDouble>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  a_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  alpha: f64,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  beta: f64,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_syrk(
+    layout,
+    a_transpose,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_triangle(
+      layout,
+      triangle,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::zscal(
+            queue,
+            len,
+            complex_f64(beta, 0.0),
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::zherk(
+    queue,
+    layout,
+    triangle,
+    a_transpose,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Shared checks for `syr2k`/`her2k`: [`check_syrk`], plus `b` has the same
+/// shape as `a`.
+#[allow(clippy::too_many_arguments)]
+fn check_syr2k<T: OclPrm>(
+  layout: CLBlastLayout,
+  ab_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+) -> Result<(), CLBlastError> {
+  check_syrk(
+    layout,
+    ab_transpose,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  check_dims(&[b_ld])?;
+  let (b_rows, b_cols) = match ab_transpose {
+    CLBlastTranspose::No => (n, k),
+    CLBlastTranspose::Yes | CLBlastTranspose::Conjugate => (k, n),
+  };
+  check_matrix(
+    b_buffer.len(),
+    layout,
+    b_rows,
+    b_cols,
+    b_offset,
+    b_ld,
+    CLBlastError::InvalidLeadDimB,
+  )
+}
+
+/// Hermitian rank-2k update (single complex), validated:
+/// `C = alpha * A * B^H + conj(alpha) * B * A^H + beta * C`
+/// (`Transpose::No`, `a` and `b` are `n x k`) or the same with `A^H * B`
+/// (`Transpose::Conjugate`, `a` and `b` are `k x n`).
+///
+/// `alpha` is complex but `beta` is real, as in BLAS. Only the triangle
+/// selected by `triangle` is read and written.
+#[allow(clippy::too_many_arguments)]
+pub fn cher2k<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  ab_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  alpha: ComplexF32,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f32,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_syr2k(
+    layout,
+    ab_transpose,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_triangle(
+      layout,
+      triangle,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::cscal(
+            queue,
+            len,
+            complex_f32(beta, 0.0),
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::cher2k(
+    queue,
+    layout,
+    triangle,
+    ab_transpose,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Hermitian rank-2k update (double complex), validated. See [`cher2k`];
+/// `alpha` is complex and `beta` real.
+#[allow(clippy::too_many_arguments)]
+pub fn zher2k<'a, T: Element<Precision = precision::ComplexDouble>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  ab_transpose: CLBlastTranspose,
+  n: usize,
+  k: usize,
+  alpha: ComplexF64,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+  beta: f64,
+  c_buffer: &Buffer<T>,
+  c_offset: usize,
+  c_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_syr2k(
+    layout,
+    ab_transpose,
+    n,
+    k,
+    a_buffer,
+    a_offset,
+    a_ld,
+    b_buffer,
+    b_offset,
+    b_ld,
+    c_buffer,
+    c_offset,
+    c_ld,
+  )?;
+  if n == 0 {
+    return Ok(None);
+  }
+  if k == 0 {
+    if beta == 1.0 {
+      return Ok(None);
+    }
+    return scale_triangle(
+      layout,
+      triangle,
+      n,
+      c_offset,
+      c_ld,
+      wait_for,
+      |len, offset, wait| {
+        if beta == 0.0 {
+          zero_fill(queue, c_buffer, offset, len, wait.iter().copied())
+        } else {
+          crate::zscal(
+            queue,
+            len,
+            complex_f64(beta, 0.0),
+            c_buffer,
+            Offset(offset),
+            1,
+            wait.iter().copied(),
+          )
+        }
+      },
+    );
+  }
+  Ok(crate::zher2k(
+    queue,
+    layout,
+    triangle,
+    ab_transpose,
+    n,
+    k,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    beta,
+    c_buffer,
+    Offset(c_offset),
+    Ld(c_ld),
+    wait_for,
+  )?)
+}
+
+/// Shared checks for `trmm`/`trsm`: `a` is square (`m x m` for
+/// `Side::Left`, `n x n` for `Side::Right`), `b` is `m x n` and the two are
+/// distinct buffers.
+#[allow(clippy::too_many_arguments)]
+fn check_trmm<T: OclPrm>(
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  m: usize,
+  n: usize,
+  a_buffer: &Buffer<T>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<T>,
+  b_offset: usize,
+  b_ld: usize,
+) -> Result<(), CLBlastError> {
+  check_dims(&[m, n, a_ld, b_ld])?;
+  let ka = match side {
+    CLBlastSide::Left => m,
+    CLBlastSide::Right => n,
+  };
+  check_matrix(
+    a_buffer.len(),
+    layout,
+    ka,
+    ka,
+    a_offset,
+    a_ld,
+    CLBlastError::InvalidLeadDimA,
+  )?;
+  check_matrix(
+    b_buffer.len(),
+    layout,
+    m,
+    n,
+    b_offset,
+    b_ld,
+    CLBlastError::InvalidLeadDimB,
+  )?;
+  check_distinct(a_buffer.as_core(), b_buffer.as_core())
+}
+
+/// Triangular multiply, validated: `B = alpha * op(A) * B` for `Side::Left`
+/// or `B = alpha * B * op(A)` for `Side::Right`, overwriting `b`.
+///
+/// `a` must hold a square `m x m` (left) or `n x n` (right) triangular
+/// matrix; `b` is `m x n`. Only the triangle selected by `triangle` is read.
+/// `a_buffer` and `b_buffer` must be different buffers.
+#[allow(clippy::too_many_arguments)]
+pub fn strmm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  triangle: CLBlastTriangle,
+  a_transpose: CLBlastTranspose,
+  diagonal: CLBlastDiagonal,
+  m: usize,
+  n: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_trmm(
+    layout, side, m, n, a_buffer, a_offset, a_ld, b_buffer, b_offset, b_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  Ok(crate::strmm(
+    queue,
+    layout,
+    side,
+    triangle,
+    a_transpose,
+    diagonal,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    wait_for,
+  )?)
+}
+
+/// Triangular solve, validated: solves `op(A) * X = alpha * B` for
+/// `Side::Left` or `X * op(A) = alpha * B` for `Side::Right`, overwriting
+/// `b` with `X`.
+///
+/// `a` must hold a square `m x m` (left) or `n x n` (right) triangular
+/// matrix; `b` is `m x n`. Only the triangle selected by `triangle` is read.
+/// `a_buffer` and `b_buffer` must be different buffers.
+#[allow(clippy::too_many_arguments)]
+pub fn strsm<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  side: CLBlastSide,
+  triangle: CLBlastTriangle,
+  a_transpose: CLBlastTranspose,
+  diagonal: CLBlastDiagonal,
+  m: usize,
+  n: usize,
+  alpha: f32,
+  a_buffer: &Buffer<f32>,
+  a_offset: usize,
+  a_ld: usize,
+  b_buffer: &Buffer<f32>,
+  b_offset: usize,
+  b_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_trmm(
+    layout, side, m, n, a_buffer, a_offset, a_ld, b_buffer, b_offset, b_ld,
+  )?;
+  if m == 0 || n == 0 {
+    return Ok(None);
+  }
+  Ok(crate::strsm(
+    queue,
+    layout,
+    side,
+    triangle,
+    a_transpose,
+    diagonal,
+    m,
+    n,
+    alpha,
+    a_buffer,
+    Offset(a_offset),
+    Ld(a_ld),
+    b_buffer,
+    Offset(b_offset),
+    Ld(b_ld),
+    wait_for,
+  )?)
+}
@@ -0,0 +1,69 @@
+//! Allocating helpers built on the Level-3 extension routines.
+//!
+//! Unlike the overlays in [`crate::level3`], these create their output
+//! buffer on the given queue and return it, so they take an [`ocl::Queue`]
+//! rather than any [`crate::AsQueue`].
+
+use ocl::{Buffer, Queue};
+
+use crate::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+use crate::error::CLBlastError;
+use crate::validate::{check_dims, check_matrix};
+use crate::{CoreEvent, Ld, Offset};
+
+/// Transpose of the `m x n` matrix `a` (starting at element 0, leading
+/// dimension `a_ld`) into a new, densely packed `n x m` buffer in the same
+/// layout, via `somatcopy` with `alpha = 1`.
+///
+/// The output's leading dimension is `m` for `RowMajor` and `n` for
+/// `ColMajor`. Returns the buffer together with the copy's completion
+/// event; read it with `ewait` on that event or after the queue finishes.
+/// An empty matrix is rejected with `InvalidDimension`, since OpenCL
+/// cannot allocate a zero-length buffer.
+pub fn transpose<'a>(
+  queue: &Queue,
+  layout: CLBlastLayout,
+  m: usize,
+  n: usize,
+  a_buffer: &Buffer<f32>,
+  a_ld: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<(Buffer<f32>, Option<CoreEvent>), CLBlastError> {
+  if m == 0 || n == 0 {
+    return Err(CLBlastError::InvalidDimension);
+  }
+  check_dims(&[m, n, a_ld])?;
+  check_matrix(
+    a_buffer.len(),
+    layout,
+    m,
+    n,
+    0,
+    a_ld,
+    CLBlastError::InvalidLeadDimA,
+  )?;
+  let b_ld = match layout {
+    CLBlastLayout::RowMajor => m,
+    CLBlastLayout::ColMajor => n,
+  };
+  let b_buffer = Buffer::<f32>::builder()
+    .queue(queue.clone())
+    .len(m * n)
+    .build()?;
+  let event = crate::somatcopy(
+    queue,
+    layout,
+    CLBlastTranspose::Yes,
+    m,
+    n,
+    1.0,
+    a_buffer,
+    Offset(0),
+    Ld(a_ld),
+    &b_buffer,
+    Offset(0),
+    Ld(b_ld),
+    wait_for,
+  )?;
+  Ok((b_buffer, event))
+}
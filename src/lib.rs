@@ -3,7 +3,91 @@
 #![allow(non_snake_case)]
 #![allow(clippy::all)]
 
+// `clblast_out_dir` is set by build.rs when it regenerates the bindings and
+// wrappers into OUT_DIR (`generate-bindings`, CLBLAST_REFRESH_WRAPPERS=1 or
+// missing files in `src/`); otherwise the committed `src/` copies are used.
+#[cfg(not(clblast_out_dir))]
 pub mod clblast_sys {
   include!("bindings_static.rs");
 }
+#[cfg(clblast_out_dir)]
+pub mod clblast_sys {
+  include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+#[cfg(not(clblast_out_dir))]
 include!("clblast_ocl_wrap.rs");
+#[cfg(clblast_out_dir)]
+include!(concat!(env!("OUT_DIR"), "/clblast_ocl_wrap.rs"));
+// Set by build.rs when the kept `src/clblast_ocl_wrap.rs` calls routines the
+// `clblast_c.h` of the CLBlast being built or linked does not declare.
+#[cfg(clblast_stale_wrappers)]
+include!(concat!(env!("OUT_DIR"), "/stale_wrappers.rs"));
+
+mod args;
+#[cfg(feature = "bench-api")]
+pub mod bench;
+#[cfg(all(feature = "level1", feature = "level3"))]
+mod blas;
+#[cfg(feature = "level3")]
+pub mod builder;
+pub mod caps;
+// The conversions are only used by the enabled `c*`/`z*` wrappers.
+#[allow(dead_code)]
+mod complex;
+pub mod enums;
+pub mod error;
+// The conversions are only used by the enabled `h*` wrappers.
+#[allow(dead_code)]
+mod float16;
+#[cfg(feature = "level3")]
+pub mod generic;
+#[cfg(feature = "nalgebra")]
+pub mod interop_nalgebra;
+#[cfg(feature = "ndarray")]
+pub mod interop_ndarray;
+#[cfg(feature = "level1")]
+pub mod level1;
+#[cfg(feature = "level2")]
+pub mod level2;
+#[cfg(feature = "level3")]
+pub mod level3;
+#[cfg(feature = "extra")]
+pub mod level3_extra;
+mod mem;
+#[cfg(feature = "level2")]
+pub mod packed;
+mod queue;
+pub mod status;
+pub mod sync;
+#[cfg(all(feature = "level2", feature = "level3"))]
+pub mod tuning;
+// Only the overlays of the enabled routine groups use it.
+#[allow(dead_code)]
+mod validate;
+mod version;
+
+pub use args::{Ld, Offset};
+#[cfg(all(feature = "level1", feature = "level3"))]
+pub use blas::Blas;
+pub use complex::{ComplexF32, ComplexF64, complex_f32, complex_f64};
+pub use error::CLBlastError;
+#[cfg(feature = "f16")]
+pub use float16::F16;
+pub use float16::Half;
+#[cfg(feature = "level3")]
+pub use generic::{ClblastFloat, ClblastScalar, gemm};
+#[cfg(feature = "raw-api")]
+pub use mem::RawMem;
+pub use mem::{AsMem, Element, precision};
+#[cfg(feature = "complex")]
+pub use num_complex::{Complex32, Complex64};
+#[cfg(feature = "raw-api")]
+pub use queue::RawQueue;
+pub use queue::{AsQueue, default_queue};
+#[cfg(all(feature = "level1", feature = "level3"))]
+pub use sync::BlasPool;
+pub use sync::{AutoWait, WaitOnDrop, profile};
+pub use version::CLBLAST_VERSION;
+
+/// Result of the generated wrappers and the validated overlays.
+pub type Result<T> = std::result::Result<T, CLBlastError>;
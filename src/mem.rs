@@ -0,0 +1,205 @@
+//! Buffer abstraction accepted by every generated wrapper.
+
+#[cfg(feature = "raw-api")]
+use std::marker::PhantomData;
+
+use ocl::prm::{Double2, Float2};
+use ocl::{Buffer, OclPrm};
+
+/// Marker types naming the element layout a routine reads and writes.
+pub mod precision {
+  /// `f32` elements (`s*` routines).
+  pub enum Single {}
+  /// `f64` elements (`d*` routines).
+  pub enum Double {}
+  /// Two `f32`s per element (`c*` routines).
+  pub enum ComplexSingle {}
+  /// Two `f64`s per element (`z*` routines).
+  pub enum ComplexDouble {}
+  /// `cl_half` elements (`h*` routines).
+  pub enum Half {}
+  /// The `unsigned int` result of `i*amax`/`i*amin`.
+  pub enum Index {}
+}
+
+/// A buffer element type and the CLBlast element layout it matches.
+pub trait Element: OclPrm {
+  /// One of the [`precision`] markers.
+  type Precision;
+}
+
+impl Element for f32 {
+  type Precision = precision::Single;
+}
+
+impl Element for f64 {
+  type Precision = precision::Double;
+}
+
+impl Element for Float2 {
+  type Precision = precision::ComplexSingle;
+}
+
+impl Element for Double2 {
+  type Precision = precision::ComplexDouble;
+}
+
+#[cfg(feature = "complex")]
+impl Element for num_complex::Complex<f32> {
+  type Precision = precision::ComplexSingle;
+}
+
+#[cfg(feature = "complex")]
+impl Element for num_complex::Complex<f64> {
+  type Precision = precision::ComplexDouble;
+}
+
+/// Raw `cl_half` bits.
+impl Element for u16 {
+  type Precision = precision::Half;
+}
+
+#[cfg(feature = "f16")]
+impl Element for crate::F16 {
+  type Precision = precision::Half;
+}
+
+impl Element for u32 {
+  type Precision = precision::Index;
+}
+
+/// Anything that can hand out an OpenCL memory object to pass to CLBlast.
+///
+/// Implemented for [`ocl::Buffer`]; with the `raw-api` feature,
+/// [`RawMem`] adopts a bare `cl_mem` from another OpenCL binding (e.g.
+/// `opencl3`), so together with [`crate::RawQueue`] the generated wrappers
+/// need no `ocl` objects at all.
+///
+/// Every data buffer must hold the [`Element`]s of the routine's precision,
+/// so a buffer of the wrong element type no longer compiles, whether mixed
+/// with others or not:
+///
+/// ```compile_fail
+/// fn mixed(
+///   queue: &ocl::Queue,
+///   x: &ocl::Buffer<f32>,
+///   y: &ocl::Buffer<f64>,
+/// ) -> clblast_binding::Result<()> {
+///   use clblast_binding::Offset;
+///   clblast_binding::saxpy(queue, 4, 1.0, x, Offset(0), 1, y, Offset(0), 1, &[])?;
+///   Ok(())
+/// }
+/// ```
+///
+/// ```compile_fail
+/// fn double_for_single(
+///   queue: &ocl::Queue,
+///   a: &ocl::Buffer<f64>,
+///   b: &ocl::Buffer<f64>,
+///   c: &ocl::Buffer<f64>,
+/// ) -> clblast_binding::Result<()> {
+///   use clblast_binding::clblast_sys::{CLBlastLayout, CLBlastTranspose};
+///   use clblast_binding::{Ld, Offset};
+///   let n = CLBlastTranspose::No;
+///   clblast_binding::sgemm(
+///     queue,
+///     CLBlastLayout::RowMajor,
+///     n,
+///     n,
+///     2,
+///     2,
+///     2,
+///     1.0,
+///     a,
+///     Offset(0),
+///     Ld(2),
+///     b,
+///     Offset(0),
+///     Ld(2),
+///     0.0,
+///     c,
+///     Offset(0),
+///     Ld(2),
+///     &[],
+///   )?;
+///   Ok(())
+/// }
+/// ```
+///
+/// The index of `i_samax` and friends must hold `u32`s and the real result
+/// of `scnrm2`-style reductions the matching real type; the scratch of
+/// `*_with_temp_buffer` is sized in bytes and takes any element type.
+///
+/// # Safety
+///
+/// The trait is `unsafe` because the wrappers hand the handle to CLBlast
+/// unchecked. An implementation must return a valid memory object holding
+/// `Self::Elem`s, alive for as long as `&self` is borrowed and until every
+/// command enqueued on it has completed.
+pub unsafe trait AsMem {
+  /// Element type the memory object holds.
+  type Elem;
+
+  /// Raw `cl_mem` handle. The handle is borrowed, not retained.
+  fn as_raw_mem(&self) -> cl_sys::cl_mem;
+}
+
+// Safety: the buffer owns a retained memory object of `T`s, which OpenCL
+// keeps alive until the commands using it finish.
+unsafe impl<T: OclPrm> AsMem for Buffer<T> {
+  type Elem = T;
+
+  fn as_raw_mem(&self) -> cl_sys::cl_mem {
+    self.as_core().as_ptr()
+  }
+}
+
+/// A `cl_mem` created outside `ocl`, usable with every generated wrapper
+/// whose precision matches `T`.
+///
+/// `RawMem` neither retains nor releases the handle: the caller keeps
+/// ownership and must keep the memory object alive until every command
+/// using it has completed.
+#[cfg(feature = "raw-api")]
+#[derive(Debug)]
+pub struct RawMem<T>(cl_sys::cl_mem, PhantomData<T>);
+
+#[cfg(feature = "raw-api")]
+impl<T> Clone for RawMem<T> {
+  fn clone(&self) -> RawMem<T> {
+    *self
+  }
+}
+
+#[cfg(feature = "raw-api")]
+impl<T> Copy for RawMem<T> {}
+
+#[cfg(feature = "raw-api")]
+impl<T> RawMem<T> {
+  /// Adopt `mem` without retaining it.
+  ///
+  /// # Safety
+  ///
+  /// `mem` must be a valid memory object on the context of the queue it is
+  /// used with, hold elements of type `T`, be large enough for every routine
+  /// it is passed to, and remain valid (not released) for every use of the
+  /// returned value.
+  pub unsafe fn from_raw(mem: cl_sys::cl_mem) -> RawMem<T> {
+    RawMem(mem, PhantomData)
+  }
+
+  /// The borrowed handle.
+  pub fn as_raw(&self) -> cl_sys::cl_mem {
+    self.0
+  }
+}
+
+// Safety: `RawMem::from_raw` requires a live memory object of `T`s.
+#[cfg(feature = "raw-api")]
+unsafe impl<T> AsMem for RawMem<T> {
+  type Elem = T;
+
+  fn as_raw_mem(&self) -> cl_sys::cl_mem {
+    self.0
+  }
+}
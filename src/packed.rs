@@ -0,0 +1,223 @@
+//! Validated overlays for packed-storage routines.
+//!
+//! A packed triangular (or symmetric/hermitian) matrix of order `n` stores
+//! only one triangle, column by column (column-major) or row by row
+//! (row-major), in a 1-D array of [`packed_len`]`(n) = n * (n + 1) / 2`
+//! elements. Each function checks that `ap_offset + packed_len(n)` fits in
+//! the `ap` buffer and that the vector extents fit, then forwards to the
+//! generated wrapper of the same name.
+//!
+//! Element `(i, j)` of the stored triangle lives at
+//! `ap[i + j * (j + 1) / 2]` for column-major upper (`i <= j`) and at
+//! `ap[i + (2 * n - j - 1) * j / 2]` for column-major lower (`i >= j`);
+//! row-major upper/lower are column-major lower/upper of the transpose.
+//! For hermitian routines the imaginary parts of the diagonal are assumed to
+//! be zero and are not read (as in reference BLAS).
+
+use ocl::Buffer;
+
+use crate::clblast_sys::{CLBlastDiagonal, CLBlastLayout, CLBlastTranspose, CLBlastTriangle};
+use crate::error::CLBlastError;
+use crate::mem::{Element, precision};
+use crate::queue::AsQueue;
+use crate::validate::{check_distinct, check_packed, check_vector};
+use crate::{ComplexF32, CoreEvent, Offset};
+
+/// Number of elements in a packed triangle of order `n`: `n * (n + 1) / 2`.
+pub const fn packed_len(n: usize) -> usize {
+  n * (n + 1) / 2
+}
+
+/// Packed symmetric rank-1 update `A = alpha * x * x^T + A`, validated.
+#[allow(clippy::too_many_arguments)]
+pub fn sspr<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  alpha: f32,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  ap_buffer: &Buffer<f32>,
+  ap_offset: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_packed(ap_buffer.len(), n, ap_offset)?;
+  Ok(crate::sspr(
+    queue,
+    layout,
+    triangle,
+    n,
+    alpha,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    ap_buffer,
+    Offset(ap_offset),
+    wait_for,
+  )?)
+}
+
+/// Packed hermitian rank-1 update `A = alpha * x * x^H + A` (single complex),
+/// validated. `alpha` is real.
+///
+/// Buffer lengths are counted in elements of `T`; use a two-component type
+/// such as `ocl::prm::Float2`.
+#[allow(clippy::too_many_arguments)]
+pub fn chpr<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  alpha: f32,
+  x_buffer: &Buffer<T>,
+  x_offset: usize,
+  x_inc: usize,
+  ap_buffer: &Buffer<T>,
+  ap_offset: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_packed(ap_buffer.len(), n, ap_offset)?;
+  Ok(crate::chpr(
+    queue,
+    layout,
+    triangle,
+    n,
+    alpha,
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    ap_buffer,
+    Offset(ap_offset),
+    wait_for,
+  )?)
+}
+
+/// Packed symmetric matrix-vector product `y = alpha * A * x + beta * y`,
+/// validated. `x` and `y` have `n` elements.
+#[allow(clippy::too_many_arguments)]
+pub fn sspmv<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  alpha: f32,
+  ap_buffer: &Buffer<f32>,
+  ap_offset: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  beta: f32,
+  y_buffer: &Buffer<f32>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_packed(ap_buffer.len(), n, ap_offset)?;
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  Ok(crate::sspmv(
+    queue,
+    layout,
+    triangle,
+    n,
+    alpha,
+    ap_buffer,
+    Offset(ap_offset),
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    beta,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// Packed hermitian matrix-vector product `y = alpha * A * x + beta * y`
+/// (single complex), validated. `x` and `y` have `n` elements.
+///
+/// Only the triangle selected by `triangle` is stored; the other one is its
+/// conjugate transpose. The imaginary parts of the diagonal are assumed to be
+/// zero and are ignored.
+#[allow(clippy::too_many_arguments)]
+pub fn chpmv<'a, T: Element<Precision = precision::ComplexSingle>>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  n: usize,
+  alpha: ComplexF32,
+  ap_buffer: &Buffer<T>,
+  ap_offset: usize,
+  x_buffer: &Buffer<T>,
+  x_offset: usize,
+  x_inc: usize,
+  beta: ComplexF32,
+  y_buffer: &Buffer<T>,
+  y_offset: usize,
+  y_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_packed(ap_buffer.len(), n, ap_offset)?;
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_vector(y_buffer.len(), n, y_offset, y_inc)?;
+  Ok(crate::chpmv(
+    queue,
+    layout,
+    triangle,
+    n,
+    alpha,
+    ap_buffer,
+    Offset(ap_offset),
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    beta,
+    y_buffer,
+    Offset(y_offset),
+    y_inc,
+    wait_for,
+  )?)
+}
+
+/// Packed triangular matrix-vector product `x = op(A) * x`, validated.
+///
+/// `x` is overwritten in place, so `ap_buffer` and `x_buffer` must be
+/// different buffers (checked with the `alias-check` feature).
+#[allow(clippy::too_many_arguments)]
+pub fn stpmv<'a>(
+  queue: &impl AsQueue,
+  layout: CLBlastLayout,
+  triangle: CLBlastTriangle,
+  a_transpose: CLBlastTranspose,
+  diagonal: CLBlastDiagonal,
+  n: usize,
+  ap_buffer: &Buffer<f32>,
+  ap_offset: usize,
+  x_buffer: &Buffer<f32>,
+  x_offset: usize,
+  x_inc: usize,
+  wait_for: impl IntoIterator<Item = &'a CoreEvent>,
+) -> Result<Option<CoreEvent>, CLBlastError> {
+  check_packed(ap_buffer.len(), n, ap_offset)?;
+  check_vector(x_buffer.len(), n, x_offset, x_inc)?;
+  check_distinct(ap_buffer.as_core(), x_buffer.as_core())?;
+  Ok(crate::stpmv(
+    queue,
+    layout,
+    triangle,
+    a_transpose,
+    diagonal,
+    n,
+    ap_buffer,
+    Offset(ap_offset),
+    x_buffer,
+    Offset(x_offset),
+    x_inc,
+    wait_for,
+  )?)
+}
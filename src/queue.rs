@@ -0,0 +1,317 @@
+//! Queue abstraction accepted by every wrapper.
+
+use std::cell::RefCell;
+
+use ocl::Device;
+use ocl::core::CommandQueue as CommandQueueCore;
+#[cfg(feature = "precision-check")]
+use ocl::core::DeviceId as DeviceIdCore;
+use ocl::{Context, Platform, ProQue, Queue};
+
+#[cfg(feature = "precision-check")]
+use crate::error::CLBlastError;
+use crate::status::opencl_error_name;
+
+/// Anything that can hand out an OpenCL command queue to enqueue on.
+///
+/// Implemented for [`ocl::Queue`], [`ocl::ProQue`], [`crate::Blas`] and the
+/// low-level `ocl::core::CommandQueue`, so `sgemm(&pro_que, ...)` works
+/// without calling `.queue()` first and code built on `ocl::core` does not
+/// need to reconstruct a high-level `Queue`. With the `raw-api` feature,
+/// `RawQueue` adopts a bare `cl_command_queue` from foreign code.
+///
+/// # Safety
+///
+/// The trait is `unsafe` because every wrapper hands the handle straight to
+/// OpenCL and CLBlast. An implementation must return a valid, live command
+/// queue for as long as `&self` is borrowed.
+pub unsafe trait AsQueue {
+  /// Raw `cl_command_queue` handle. The handle is borrowed, not retained.
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue;
+}
+
+// Safety: `ocl::Queue` owns a retained queue.
+unsafe impl AsQueue for Queue {
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue {
+    self.as_core().as_ptr()
+  }
+}
+
+// Safety: `CommandQueueCore` owns a retained queue.
+unsafe impl AsQueue for CommandQueueCore {
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue {
+    self.as_ptr()
+  }
+}
+
+// Safety: the `ProQue` owns a retained queue.
+unsafe impl AsQueue for ProQue {
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue {
+    self.queue().as_raw_queue()
+  }
+}
+
+/// A `cl_command_queue` created outside `ocl`, e.g. by a C/C++ host
+/// application, usable with every free function without building an
+/// `ocl::Context`/`Queue` around it.
+///
+/// `RawQueue` neither retains nor releases the handle: the caller keeps
+/// ownership and must keep the queue alive for as long as the `RawQueue`
+/// (or any copy of it) is used.
+#[cfg(feature = "raw-api")]
+#[derive(Debug, Clone, Copy)]
+pub struct RawQueue(cl_sys::cl_command_queue);
+
+#[cfg(feature = "raw-api")]
+impl RawQueue {
+  /// Adopt `queue` without retaining it.
+  ///
+  /// # Safety
+  ///
+  /// `queue` must be a valid command queue, and must remain valid (not
+  /// released) for every use of the returned value.
+  pub unsafe fn from_raw(queue: cl_sys::cl_command_queue) -> RawQueue {
+    RawQueue(queue)
+  }
+
+  /// The borrowed handle.
+  pub fn as_raw(&self) -> cl_sys::cl_command_queue {
+    self.0
+  }
+}
+
+// Safety: `RawQueue::from_raw` requires a live queue.
+#[cfg(feature = "raw-api")]
+unsafe impl AsQueue for RawQueue {
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue {
+    self.0
+  }
+}
+
+/// An in-order queue on the device picked by the `CLBLAST_PLATFORM_INDEX`
+/// and `CLBLAST_DEVICE_INDEX` environment variables.
+///
+/// Each index counts from zero in the order `ocl::Platform::list()` and
+/// `ocl::Device::list_all()` report them; an unset variable falls back to
+/// `Platform::default()` and `Device::first()`. Used by the crate's own
+/// tests so CI can pin them to a particular GPU.
+pub fn default_queue() -> ocl::Result<Queue> {
+  let platform = match env_index("CLBLAST_PLATFORM_INDEX")? {
+    Some(i) => {
+      let platforms = Platform::list();
+      let count = platforms.len();
+      platforms.into_iter().nth(i).ok_or_else(|| {
+        ocl::Error::from(format!(
+          "CLBLAST_PLATFORM_INDEX={i} but only {count} platform(s) found"
+        ))
+      })?
+    }
+    None => Platform::default(),
+  };
+  let device = match env_index("CLBLAST_DEVICE_INDEX")? {
+    Some(i) => {
+      let devices = Device::list_all(platform)?;
+      let count = devices.len();
+      devices.into_iter().nth(i).ok_or_else(|| {
+        ocl::Error::from(format!(
+          "CLBLAST_DEVICE_INDEX={i} but only {count} device(s) found"
+        ))
+      })?
+    }
+    None => Device::first(platform)?,
+  };
+  let context = Context::builder()
+    .platform(platform)
+    .devices(device)
+    .build()?;
+  Queue::new(&context, device, None)
+}
+
+/// `Some(index)` if `var` is set, an error if it is not a valid index.
+fn env_index(var: &str) -> ocl::Result<Option<usize>> {
+  match std::env::var(var) {
+    Ok(value) => value
+      .trim()
+      .parse()
+      .map(Some)
+      .map_err(|_| ocl::Error::from(format!("{var}={value:?} is not a valid index"))),
+    Err(_) => Ok(None),
+  }
+}
+
+/// A `clGetCommandQueueInfo` value of plain-data type `T`.
+#[allow(dead_code)]
+fn queue_info<T: Copy>(
+  queue: cl_sys::cl_command_queue,
+  param: cl_sys::cl_command_queue_info,
+  init: T,
+) -> ocl::Result<T> {
+  let mut value = init;
+  let err = unsafe {
+    cl_sys::clGetCommandQueueInfo(
+      queue,
+      param,
+      size_of::<T>(),
+      &mut value as *mut T as *mut std::ffi::c_void,
+      std::ptr::null_mut(),
+    )
+  };
+  if err != cl_sys::CL_SUCCESS {
+    return Err(ocl::Error::from(format!(
+      "clGetCommandQueueInfo failed: {} ({})",
+      opencl_error_name(err),
+      err
+    )));
+  }
+  Ok(value)
+}
+
+/// The device `queue` enqueues on.
+#[cfg(feature = "precision-check")]
+pub(crate) fn queue_device(queue: &impl AsQueue) -> Result<Device, CLBlastError> {
+  let device = queue_info(
+    queue.as_raw_queue(),
+    cl_sys::CL_QUEUE_DEVICE,
+    std::ptr::null_mut::<std::ffi::c_void>() as cl_sys::cl_device_id,
+  )?;
+  Ok(Device::from(unsafe { DeviceIdCore::from_raw(device) }))
+}
+
+/// Most queues [`in_order_queue`] remembers per thread. Beyond it, the least
+/// recently used one is released.
+const QUEUE_CACHE_LIMIT: usize = 8;
+
+/// What [`in_order_queue`] learned about one queue.
+struct CachedQueue {
+  /// The queue, retained so its handle cannot be reused while cached.
+  queue: CommandQueueCore,
+  /// An in-order stand-in on the same context and device if `queue`
+  /// executes out of order.
+  scratch: Option<CommandQueueCore>,
+}
+
+thread_local! {
+  /// Queues seen by [`in_order_queue`] on this thread, most recently used
+  /// last, so repeated calls on a queue skip the `clGetCommandQueueInfo`.
+  static QUEUE_CACHE: RefCell<Vec<CachedQueue>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The queue a generated wrapper enqueues on, from [`in_order_queue`].
+/// Flushes the scratch queue on drop so its commands get submitted even if
+/// only events from other queues are ever waited on.
+#[allow(dead_code)]
+pub(crate) struct InOrderQueue {
+  raw: cl_sys::cl_command_queue,
+  /// The scratch queue, retained in case the cache drops it meanwhile.
+  scratch: Option<CommandQueueCore>,
+}
+
+// Safety: `raw` is either the caller's queue, live while the wrapper
+// borrows it, or the retained `scratch`.
+unsafe impl AsQueue for InOrderQueue {
+  fn as_raw_queue(&self) -> cl_sys::cl_command_queue {
+    self.raw
+  }
+}
+
+impl Drop for InOrderQueue {
+  fn drop(&mut self) {
+    if self.scratch.is_some() {
+      unsafe { cl_sys::clFlush(self.raw) };
+    }
+  }
+}
+
+/// `queue` itself if it executes in order, otherwise an in-order queue on
+/// the same context and device (created once per queue and thread, then
+/// reused).
+///
+/// The marker wrappers enqueue for `wait_for` only orders the routine after
+/// it on an in-order queue; on an out-of-order one CLBlast's kernels could
+/// start before their inputs are ready. The returned event belongs to the
+/// scratch queue and can be waited on or passed to `wait_for` as usual.
+/// Under [`crate::sync::assume_in_order`] the check is skipped.
+///
+/// The answer is cached per thread for the last [`QUEUE_CACHE_LIMIT`]
+/// queues, each retained (with its scratch queue) until it is evicted or
+/// [`crate::sync::release_queue_cache`] is called.
+#[allow(dead_code)]
+pub(crate) fn in_order_queue(queue: &impl AsQueue) -> ocl::Result<InOrderQueue> {
+  let raw = queue.as_raw_queue();
+  if crate::sync::assumes_in_order() {
+    return Ok(InOrderQueue { raw, scratch: None });
+  }
+  QUEUE_CACHE.with(|cache| {
+    let mut cache = cache.borrow_mut();
+    let entry = match cache.iter().position(|entry| entry.queue.as_ptr() == raw) {
+      Some(i) => cache.remove(i),
+      None => {
+        if cache.len() >= QUEUE_CACHE_LIMIT {
+          cache.remove(0);
+        }
+        inspect_queue(raw)?
+      }
+    };
+    let scratch = entry.scratch.clone();
+    cache.push(entry);
+    Ok(match scratch {
+      Some(scratch) => InOrderQueue {
+        raw: scratch.as_ptr(),
+        scratch: Some(scratch),
+      },
+      None => InOrderQueue { raw, scratch: None },
+    })
+  })
+}
+
+/// Retain `raw` and, if it executes out of order, create its scratch queue.
+fn inspect_queue(raw: cl_sys::cl_command_queue) -> ocl::Result<CachedQueue> {
+  let queue = unsafe { CommandQueueCore::from_raw_copied_ptr(raw) };
+  let properties: cl_sys::cl_command_queue_properties =
+    queue_info(raw, cl_sys::CL_QUEUE_PROPERTIES, 0)?;
+  if properties & cl_sys::CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE == 0 {
+    return Ok(CachedQueue {
+      queue,
+      scratch: None,
+    });
+  }
+  let null = std::ptr::null_mut::<std::ffi::c_void>();
+  let context = queue_info(raw, cl_sys::CL_QUEUE_CONTEXT, null as cl_sys::cl_context)?;
+  let device = queue_info(raw, cl_sys::CL_QUEUE_DEVICE, null as cl_sys::cl_device_id)?;
+  // Keep every other property (profiling in particular) of the original.
+  let in_order = properties & !cl_sys::CL_QUEUE_OUT_OF_ORDER_EXEC_MODE_ENABLE;
+  let mut err = cl_sys::CL_SUCCESS;
+  let created = unsafe { cl_sys::clCreateCommandQueue(context, device, in_order, &mut err) };
+  if err != cl_sys::CL_SUCCESS {
+    return Err(ocl::Error::from(format!(
+      "clCreateCommandQueue failed: {} ({})",
+      opencl_error_name(err),
+      err
+    )));
+  }
+  Ok(CachedQueue {
+    queue,
+    scratch: Some(unsafe { CommandQueueCore::from_raw_create_ptr(created) }),
+  })
+}
+
+/// `clFinish` the scratch queue [`in_order_queue`] cached on this thread
+/// for `raw`, if any.
+pub(crate) fn finish_scratch(raw: cl_sys::cl_command_queue) -> cl_sys::cl_int {
+  let scratch = QUEUE_CACHE.with(|cache| {
+    cache
+      .borrow()
+      .iter()
+      .find(|entry| entry.queue.as_ptr() == raw)
+      .and_then(|entry| entry.scratch.clone())
+  });
+  match scratch {
+    Some(scratch) => unsafe { cl_sys::clFinish(scratch.as_ptr()) },
+    None => cl_sys::CL_SUCCESS,
+  }
+}
+
+/// Drop every queue [`in_order_queue`] cached on this thread.
+pub(crate) fn clear_queue_cache() {
+  QUEUE_CACHE.with(|cache| cache.borrow_mut().clear());
+}
@@ -0,0 +1,240 @@
+//! Helpers for synchronous-style code built on the returned events.
+
+use std::cell::Cell;
+#[cfg(all(feature = "level1", feature = "level3"))]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use ocl::Queue;
+use ocl::core::{ProfilingInfo, get_event_profiling_info};
+#[cfg(all(feature = "level1", feature = "level3"))]
+use ocl::{Context, Device};
+
+use crate::CoreEvent;
+#[cfg(all(feature = "level1", feature = "level3"))]
+use crate::blas::Blas;
+use crate::error::CLBlastError;
+use crate::queue::AsQueue;
+
+/// Waits for an enqueued call when dropped.
+///
+/// Holds the event returned by a wrapper (if any) and the queue it was
+/// enqueued on. On drop it waits on the event, or calls [`finish`] on the
+/// queue when there is none. Drop never panics: a failed wait is logged through
+/// `tracing` when that feature is enabled and otherwise ignored. Call
+/// [`WaitOnDrop::wait`] to observe the error instead.
+#[must_use = "dropping a WaitOnDrop immediately blocks until the call completes"]
+#[derive(Debug)]
+pub struct WaitOnDrop<'q> {
+  event: Option<CoreEvent>,
+  queue: &'q Queue,
+  done: bool,
+}
+
+impl<'q> WaitOnDrop<'q> {
+  /// Guard `event` (enqueued on `queue`).
+  pub fn new(event: Option<CoreEvent>, queue: &'q Queue) -> WaitOnDrop<'q> {
+    WaitOnDrop {
+      event,
+      queue,
+      done: false,
+    }
+  }
+
+  /// The guarded event, if the call returned one.
+  pub fn event(&self) -> Option<&CoreEvent> {
+    self.event.as_ref()
+  }
+
+  /// Wait now and report any error; nothing is left to do on drop.
+  pub fn wait(mut self) -> Result<(), CLBlastError> {
+    self.done = true;
+    self.block()
+  }
+
+  fn block(&self) -> Result<(), CLBlastError> {
+    match &self.event {
+      Some(event) => event.wait_for().map_err(ocl::Error::from)?,
+      None => finish(self.queue)?,
+    }
+    Ok(())
+  }
+}
+
+impl Drop for WaitOnDrop<'_> {
+  fn drop(&mut self) {
+    if self.done {
+      return;
+    }
+    #[allow(unused_variables)]
+    if let Err(e) = self.block() {
+      #[cfg(feature = "tracing")]
+      tracing::warn!(error = %e, "clblast: wait on drop failed");
+    }
+  }
+}
+
+/// `auto_wait` on the `Option<CoreEvent>` returned by every wrapper.
+pub trait AutoWait {
+  /// Wrap `self` in a [`WaitOnDrop`] guard for `queue`.
+  fn auto_wait(self, queue: &Queue) -> WaitOnDrop<'_>;
+}
+
+impl AutoWait for Option<CoreEvent> {
+  fn auto_wait(self, queue: &Queue) -> WaitOnDrop<'_> {
+    WaitOnDrop::new(self, queue)
+  }
+}
+
+/// Block until every command enqueued on `queue` so far has completed
+/// (`clFinish`). For code that drops the events wrappers return instead of
+/// chaining on them.
+///
+/// Routines a wrapper ran on this thread for an out-of-order `queue` went to
+/// its in-order scratch queue (see [`release_queue_cache`]); that queue is
+/// finished as well.
+pub fn finish(queue: &impl AsQueue) -> Result<(), CLBlastError> {
+  let raw = queue.as_raw_queue();
+  for err in [
+    unsafe { cl_sys::clFinish(raw) },
+    crate::queue::finish_scratch(raw),
+  ] {
+    if err != cl_sys::CL_SUCCESS {
+      return Err(CLBlastError::OpenCL(err));
+    }
+  }
+  Ok(())
+}
+
+/// GPU-side execution time of the command behind `event`
+/// (`CL_PROFILING_COMMAND_END - CL_PROFILING_COMMAND_START`).
+///
+/// Waits for the command to complete first. The queue must have been created
+/// with `CommandQueueProperties::PROFILING_ENABLE`; otherwise OpenCL reports
+/// `CL_PROFILING_INFO_NOT_AVAILABLE` and this returns an error.
+pub fn profile(event: &CoreEvent) -> Result<Duration, CLBlastError> {
+  event.wait_for().map_err(ocl::Error::from)?;
+  let time =
+    |info| -> Result<u64, ocl::Error> { Ok(get_event_profiling_info(event, info)?.time()?) };
+  let start = time(ProfilingInfo::Start)?;
+  let end = time(ProfilingInfo::End)?;
+  Ok(Duration::from_nanos(end.saturating_sub(start)))
+}
+
+thread_local! {
+  static ASSUME_IN_ORDER: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Expert switch: when `enabled`, wrappers called from this thread ignore
+/// `wait_for` and skip the marker they would otherwise enqueue for it.
+///
+/// Saves one `clEnqueueMarkerWithWaitList` per call with a non-empty
+/// `wait_for` in steady-state pipelines. Returned events are unaffected.
+///
+/// # Safety
+///
+/// While enabled, every event passed as `wait_for` on this thread must
+/// belong to a command already enqueued on the *same in-order* queue as the
+/// call. Events from another queue, another thread's queue, user events or
+/// an out-of-order queue are silently not waited on, so the routine may
+/// read its inputs before they are written: results are then garbage and
+/// buffers may be read while another command writes them. The setting is
+/// per thread and stays until changed.
+pub unsafe fn assume_in_order(enabled: bool) {
+  ASSUME_IN_ORDER.with(|flag| flag.set(enabled));
+}
+
+/// Whether [`assume_in_order`] is enabled on this thread.
+pub fn assumes_in_order() -> bool {
+  ASSUME_IN_ORDER.with(Cell::get)
+}
+
+/// Release the queues wrappers cached on this thread.
+///
+/// To honour `wait_for` on out-of-order queues, wrappers remember (and
+/// retain) the last few queues they ran on, together with an in-order
+/// scratch queue for each out-of-order one. Call this when a thread is done
+/// with a context so its queues, and through them the context, are freed
+/// before the thread exits. Later calls simply rebuild the cache.
+pub fn release_queue_cache() {
+  crate::queue::clear_queue_cache();
+}
+
+/// One [`Blas`] per device of a context, for fanning routines out across
+/// GPUs.
+///
+/// Buffers are not shared: each call must be given buffers allocated on
+/// the queue (device) of the `Blas` it is submitted to.
+#[cfg(all(feature = "level1", feature = "level3"))]
+#[derive(Debug)]
+pub struct BlasPool {
+  blas: Vec<Blas>,
+  devices: Vec<Device>,
+  next: AtomicUsize,
+}
+
+#[cfg(all(feature = "level1", feature = "level3"))]
+impl BlasPool {
+  /// An in-order queue, wrapped in a row-major [`Blas`], for every device
+  /// of `context`, in the order `context.devices()` lists them.
+  pub fn new(context: &Context) -> ocl::Result<BlasPool> {
+    let queues = context
+      .devices()
+      .into_iter()
+      .map(|device| Queue::new(context, device, None))
+      .collect::<ocl::Result<Vec<_>>>()?;
+    BlasPool::from_queues(queues)
+  }
+
+  /// A pool over existing queues, e.g. ones created with profiling
+  /// enabled. Fails if `queues` is empty.
+  pub fn from_queues(queues: Vec<Queue>) -> ocl::Result<BlasPool> {
+    if queues.is_empty() {
+      return Err(ocl::Error::from("BlasPool needs at least one queue"));
+    }
+    let devices = queues.iter().map(Queue::device).collect();
+    Ok(BlasPool {
+      blas: queues.into_iter().map(Blas::new).collect(),
+      devices,
+      next: AtomicUsize::new(0),
+    })
+  }
+
+  /// The device behind each index.
+  pub fn devices(&self) -> &[Device] {
+    &self.devices
+  }
+
+  /// Number of devices (never zero).
+  pub fn len(&self) -> usize {
+    self.blas.len()
+  }
+
+  /// Always `false`; a pool holds at least one device.
+  pub fn is_empty(&self) -> bool {
+    self.blas.is_empty()
+  }
+
+  /// The `Blas` for device `index`.
+  pub fn get(&self, index: usize) -> Option<&Blas> {
+    self.blas.get(index)
+  }
+
+  /// The next `Blas` in round-robin order. Safe to call from several
+  /// threads; each call advances the shared cursor by one.
+  pub fn next(&self) -> &Blas {
+    let index = self.next.fetch_add(1, Ordering::Relaxed) % self.blas.len();
+    &self.blas[index]
+  }
+
+  /// Calls `f` once per device, in index order, and collects the results
+  /// (typically the events to wait on).
+  pub fn for_each<R>(&self, mut f: impl FnMut(usize, &Blas) -> R) -> Vec<R> {
+    self
+      .blas
+      .iter()
+      .enumerate()
+      .map(|(i, blas)| f(i, blas))
+      .collect()
+  }
+}
@@ -0,0 +1,211 @@
+//! Kernel cache control and warm-up.
+//!
+//! CLBlast compiles each routine's kernels on first use for a given context
+//! and device, which can take hundreds of milliseconds. [`warmup`] moves that
+//! cost up front.
+//!
+//! Tuning parameters are write-only from Rust: the CLBlast C API these
+//! bindings are generated from has `CLBlastOverrideParameters`
+//! ([`override_parameters`]) but no call to read back the parameters
+//! a kernel will use, nor whether they came from the built-in database or
+//! an override. (`RetrieveParameters` exists only in the C++ API, so there
+//! is no `retrieve_parameters` here.) To restore a configuration later,
+//! keep the map passed to [`override_parameters`]; to audit an override,
+//! time the routine before and after applying it (e.g. with
+//! [`crate::profile`]).
+
+use std::collections::HashMap;
+use std::ffi::{CString, c_char};
+
+use ocl::{Buffer, Device, OclPrm, Queue};
+
+use crate::clblast_sys::{self as sys, CLBlastLayout, CLBlastPrecision, CLBlastTranspose};
+use crate::enums::Precision;
+use crate::error::CLBlastError;
+use crate::{Ld, Offset, complex_f32, complex_f64};
+
+/// Routines [`warmup`] can pre-compile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WarmupRoutine {
+  /// `xGEMM`.
+  Gemm,
+  /// `xGEMV`.
+  Gemv,
+}
+
+/// One-element scratch buffer on `queue`.
+fn scratch<T: OclPrm>(queue: &Queue) -> Result<Buffer<T>, CLBlastError> {
+  Ok(
+    Buffer::<T>::builder()
+      .queue(queue.clone())
+      .len(1)
+      .fill_val(T::default())
+      .build()?,
+  )
+}
+
+/// Run a `1 x 1` instance of `routine` in `precision` on `queue`.
+fn run_tiny(
+  queue: &Queue,
+  precision: CLBlastPrecision,
+  routine: WarmupRoutine,
+) -> Result<(), CLBlastError> {
+  macro_rules! tiny {
+    ($elem:ty, $gemm:ident, $gemv:ident, $one:expr, $zero:expr) => {{
+      let a = scratch::<$elem>(queue)?;
+      let b = scratch::<$elem>(queue)?;
+      let c = scratch::<$elem>(queue)?;
+      let (l, t) = (CLBlastLayout::RowMajor, CLBlastTranspose::No);
+      let event = match routine {
+        WarmupRoutine::Gemm => crate::$gemm(
+          queue,
+          l,
+          t,
+          t,
+          1,
+          1,
+          1,
+          $one,
+          &a,
+          Offset(0),
+          Ld(1),
+          &b,
+          Offset(0),
+          Ld(1),
+          $zero,
+          &c,
+          Offset(0),
+          Ld(1),
+          &[],
+        )?,
+        WarmupRoutine::Gemv => crate::$gemv(
+          queue,
+          l,
+          t,
+          1,
+          1,
+          $one,
+          &a,
+          Offset(0),
+          Ld(1),
+          &b,
+          Offset(0),
+          1,
+          $zero,
+          &c,
+          Offset(0),
+          1,
+          &[],
+        )?,
+      };
+      if let Some(event) = event {
+        event.wait_for().map_err(ocl::Error::from)?;
+      }
+    }};
+  }
+  match precision {
+    CLBlastPrecision::Half => tiny!(
+      sys::cl_half,
+      hgemm,
+      hgemv,
+      crate::float16::HALF_ONE_ZERO.0,
+      crate::float16::HALF_ONE_ZERO.1
+    ),
+    CLBlastPrecision::Single => tiny!(f32, sgemm, sgemv, 1.0, 0.0),
+    CLBlastPrecision::Double => tiny!(f64, dgemm, dgemv, 1.0, 0.0),
+    CLBlastPrecision::ComplexSingle => tiny!(
+      ocl::prm::Float2,
+      cgemm,
+      cgemv,
+      complex_f32(1.0, 0.0),
+      complex_f32(0.0, 0.0)
+    ),
+    CLBlastPrecision::ComplexDouble => tiny!(
+      ocl::prm::Double2,
+      zgemm,
+      zgemv,
+      complex_f64(1.0, 0.0),
+      complex_f64(0.0, 0.0)
+    ),
+  }
+  Ok(())
+}
+
+/// Drop every kernel and parameter CLBlast has cached, for all devices.
+///
+/// Later calls compile their kernels again; useful after
+/// [`override_parameters`] or to release program memory.
+pub fn clear_cache() -> Result<(), CLBlastError> {
+  crate::clear_cache()
+}
+
+/// Compile and cache CLBlast's kernels for `device` ahead of time, so the
+/// first call of a routine does not pay the compile cost.
+///
+/// This covers every routine and precision and can take seconds; for a
+/// handful of routines on one queue, [`warmup`] is cheaper.
+pub fn fill_cache(device: &Device) -> Result<(), CLBlastError> {
+  crate::fill_cache(device.as_raw() as sys::cl_device_id)
+}
+
+/// Pre-compile kernels for the device behind `queue`.
+///
+/// Fills CLBlast's cache for the device ([`fill_cache`]), then runs a
+/// `1 x 1` instance of every routine in `routines` for every precision in
+/// `precisions`, blocking until each finishes. CLBlast caches compiled
+/// programs per context and device, so warm up on a queue sharing the
+/// context used later; a private context would not help.
+///
+/// Precisions the device does not support (e.g. `Half` without
+/// `cl_khr_fp16`) fail with the CLBlast error for that call.
+pub fn warmup(
+  queue: &Queue,
+  precisions: &[CLBlastPrecision],
+  routines: &[WarmupRoutine],
+) -> Result<(), CLBlastError> {
+  let device = queue.device();
+  fill_cache(&device)?;
+  for &precision in precisions {
+    for &routine in routines {
+      run_tiny(queue, precision, routine)?;
+    }
+  }
+  Ok(())
+}
+
+/// Replace the tuning parameters of `kernel` (e.g. `"Xgemm"`) for
+/// `precision` on `device`, e.g. with the JSON output of CLBlast's tuners.
+///
+/// The override applies to every later call on the device, in this and any
+/// other context. CLBlast rejects an unknown kernel with
+/// [`CLBlastError::InvalidOverrideKernel`] and an incomplete set of
+/// parameters with [`CLBlastError::MissingOverrideParameter`]; names
+/// containing a NUL byte fail the same way without reaching CLBlast.
+pub fn override_parameters(
+  device: &Device,
+  kernel: &str,
+  precision: Precision,
+  params: &HashMap<String, usize>,
+) -> Result<(), CLBlastError> {
+  let kernel = CString::new(kernel).map_err(|_| CLBlastError::InvalidOverrideKernel)?;
+  let (names, values): (Vec<CString>, Vec<usize>) = params
+    .iter()
+    .map(|(name, &value)| Ok((CString::new(name.as_str())?, value)))
+    .collect::<Result<Vec<_>, std::ffi::NulError>>()
+    .map_err(|_| CLBlastError::MissingOverrideParameter)?
+    .into_iter()
+    .unzip();
+  // Safety: `name_ptrs` and `values` hold `params.len()` entries, and
+  // CLBlast copies the names and values before returning.
+  let mut name_ptrs: Vec<*const c_char> = names.iter().map(|name| name.as_ptr()).collect();
+  unsafe {
+    crate::override_parameters(
+      device.as_raw() as sys::cl_device_id,
+      kernel.as_ptr(),
+      precision,
+      params.len(),
+      name_ptrs.as_mut_ptr(),
+      values.as_ptr(),
+    )
+  }
+}
@@ -0,0 +1,100 @@
+//! Host-side argument checks used by the overlay modules.
+
+use ocl::core::Mem;
+
+use crate::clblast_sys::CLBlastLayout;
+use crate::error::CLBlastError;
+
+/// Number of elements a strided vector access touches:
+/// `offset + (n - 1) * inc + 1`, or `0` when `n == 0`.
+/// Returns `None` on overflow.
+pub(crate) fn vector_extent(n: usize, offset: usize, inc: usize) -> Option<usize> {
+  if n == 0 {
+    return Some(0);
+  }
+  (n - 1)
+    .checked_mul(inc)?
+    .checked_add(offset)?
+    .checked_add(1)
+}
+
+/// Check that a vector of `n` elements at `offset` with stride `inc` fits in `len`.
+pub(crate) fn check_vector(
+  len: usize,
+  n: usize,
+  offset: usize,
+  inc: usize,
+) -> Result<(), CLBlastError> {
+  match vector_extent(n, offset, inc) {
+    Some(need) if need <= len => Ok(()),
+    _ => Err(CLBlastError::InvalidBufferSize),
+  }
+}
+
+/// Check that a `rows x cols` matrix at `offset` with leading dimension `ld`
+/// fits in `len`. `ld` must be at least the contiguous dimension (`cols` for
+/// row-major, `rows` for column-major); otherwise `ld_err` is returned.
+pub(crate) fn check_matrix(
+  len: usize,
+  layout: CLBlastLayout,
+  rows: usize,
+  cols: usize,
+  offset: usize,
+  ld: usize,
+  ld_err: CLBlastError,
+) -> Result<(), CLBlastError> {
+  let (outer, inner) = match layout {
+    CLBlastLayout::RowMajor => (rows, cols),
+    CLBlastLayout::ColMajor => (cols, rows),
+  };
+  if ld < inner.max(1) {
+    return Err(ld_err);
+  }
+  if outer == 0 || inner == 0 {
+    return Ok(());
+  }
+  let need = (outer - 1)
+    .checked_mul(ld)
+    .and_then(|v| v.checked_add(offset))
+    .and_then(|v| v.checked_add(inner));
+  match need {
+    Some(need) if need <= len => Ok(()),
+    _ => Err(CLBlastError::InvalidBufferSize),
+  }
+}
+
+/// Check that a packed triangular matrix of order `n` (`n * (n + 1) / 2`
+/// elements) starting at `offset` fits in `len`.
+pub(crate) fn check_packed(len: usize, n: usize, offset: usize) -> Result<(), CLBlastError> {
+  let need = n
+    .checked_add(1)
+    .and_then(|v| v.checked_mul(n))
+    .map(|v| v / 2)
+    .and_then(|v| v.checked_add(offset));
+  match need {
+    Some(need) if need <= len => Ok(()),
+    _ => Err(CLBlastError::InvalidBufferSize),
+  }
+}
+
+/// With the `alias-check` feature, reject two arguments that are the same
+/// `cl_mem` object. Sub-buffers of one parent are distinct handles and are
+/// not detected. Without the feature this is a no-op.
+pub(crate) fn check_distinct(a: &Mem, b: &Mem) -> Result<(), CLBlastError> {
+  if cfg!(feature = "alias-check") && a.as_ptr() == b.as_ptr() {
+    return Err(CLBlastError::AliasingViolation);
+  }
+  Ok(())
+}
+
+/// Largest dimension or leading dimension CLBlast handles: its kernels index
+/// with 32-bit `int`, so anything above `i32::MAX` would wrap.
+pub const MAX_DIMENSION: usize = i32::MAX as usize;
+
+/// Check that every value in `dims` is at most [`MAX_DIMENSION`].
+pub(crate) fn check_dims(dims: &[usize]) -> Result<(), CLBlastError> {
+  if dims.iter().any(|&d| d > MAX_DIMENSION) {
+    return Err(CLBlastError::DimensionTooLarge);
+  }
+  Ok(())
+}
@@ -0,0 +1,25 @@
+//! Version of the CLBlast library this crate was built against.
+
+/// Parse a decimal `u32` at compile time (the build script only emits digits).
+const fn parse_u32(s: &str) -> u32 {
+  let bytes = s.as_bytes();
+  let mut value = 0u32;
+  let mut i = 0;
+  while i < bytes.len() {
+    value = value * 10 + (bytes[i] - b'0') as u32;
+    i += 1;
+  }
+  value
+}
+
+/// `(major, minor, patch)` of the linked CLBlast, captured by `build.rs`.
+///
+/// Taken from `CMakeLists.txt` for the bundled build and from `pkg-config`
+/// for `system-clblast`. CLBlast has no runtime version query, so this is
+/// the compile-time value; it is `(0, 0, 0)` when the version could not be
+/// determined (e.g. a vcpkg install).
+pub const CLBLAST_VERSION: (u32, u32, u32) = (
+  parse_u32(env!("CLBLAST_VERSION_MAJOR")),
+  parse_u32(env!("CLBLAST_VERSION_MINOR")),
+  parse_u32(env!("CLBLAST_VERSION_PATCH")),
+);
@@ -0,0 +1,21 @@
+#[cfg(all(test, feature = "bench-api"))]
+mod common;
+
+#[cfg(all(test, feature = "bench-api"))]
+mod tests {
+  use clblast_binding::bench;
+
+  use crate::common::profiling_queue;
+
+  #[test]
+  fn bench_sgemm_reports_positive_gflops() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(queue) = profiling_queue()? else {
+      eprintln!("skipping: profiling queues not supported");
+      return Ok(());
+    };
+    let gflops = bench::bench_sgemm(&queue, 256, 256, 256, 3)?;
+    assert!(gflops.is_finite() && gflops > 0.0, "gflops = {gflops}");
+    assert!(bench::bench_sgemm(&queue, 0, 256, 256, 3).is_err());
+    Ok(())
+  }
+}
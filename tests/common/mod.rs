@@ -0,0 +1,18 @@
+//! Helpers shared by the integration tests.
+
+use clblast_binding::default_queue;
+use ocl::Queue;
+use ocl::flags::CommandQueueProperties;
+
+/// A profiling-enabled queue, or `None` when the device refuses one.
+pub fn profiling_queue() -> ocl::Result<Option<Queue>> {
+  let queue = default_queue()?;
+  Ok(
+    Queue::new(
+      &queue.context(),
+      queue.device(),
+      Some(CommandQueueProperties::new().profiling()),
+    )
+    .ok(),
+  )
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+  use clblast_binding::clblast_sys::CLBlastLayout;
+  use clblast_binding::enums::{Diagonal, Layout, Side, Transpose, Triangle};
+  use std::collections::{BTreeMap, HashSet};
+
+  #[test]
+  fn layout_opposite() {
+    assert_eq!(Layout::RowMajor.opposite(), Layout::ColMajor);
+    assert_eq!(Layout::ColMajor.opposite(), Layout::RowMajor);
+  }
+
+  #[test]
+  fn transpose_predicates() {
+    assert!(!Transpose::No.is_transposed());
+    assert!(Transpose::Yes.is_transposed());
+    assert!(Transpose::Conjugate.is_transposed());
+
+    assert!(!Transpose::No.is_conjugate());
+    assert!(!Transpose::Yes.is_conjugate());
+    assert!(Transpose::Conjugate.is_conjugate());
+  }
+
+  #[test]
+  fn triangle_opposite() {
+    assert_eq!(Triangle::Upper.opposite(), Triangle::Lower);
+    assert_eq!(Triangle::Lower.opposite(), Triangle::Upper);
+    assert_eq!(Triangle::Upper.opposite().opposite(), Triangle::Upper);
+  }
+
+  #[test]
+  fn diagonal_is_unit() {
+    assert!(Diagonal::Unit.is_unit());
+    assert!(!Diagonal::NonUnit.is_unit());
+  }
+
+  #[test]
+  fn side_swap() {
+    assert_eq!(Side::Left.swap(), Side::Right);
+    assert_eq!(Side::Right.swap(), Side::Left);
+  }
+
+  #[test]
+  fn aliases_are_the_bindgen_types() {
+    let layout: CLBlastLayout = Layout::RowMajor;
+    assert_eq!(layout, CLBlastLayout::RowMajor);
+    assert_eq!(format!("{:?}", Transpose::Conjugate), "Conjugate");
+
+    let set: HashSet<Triangle> = [Triangle::Upper, Triangle::Lower, Triangle::Upper]
+      .into_iter()
+      .collect();
+    assert_eq!(set.len(), 2);
+  }
+
+  #[test]
+  fn enums_order_by_c_value() {
+    assert!(Layout::RowMajor < Layout::ColMajor);
+    let tuned: BTreeMap<(Layout, Transpose), usize> = [
+      ((Layout::ColMajor, Transpose::No), 2),
+      ((Layout::RowMajor, Transpose::Yes), 1),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(tuned.values().copied().collect::<Vec<_>>(), [1, 2]);
+  }
+
+  #[cfg(feature = "serde")]
+  #[test]
+  fn enums_deserialize_from_variant_names() {
+    use clblast_binding::enums::Precision;
+    use serde::Deserialize;
+    use serde::de::IntoDeserializer;
+    use serde::de::value::Error;
+
+    let precision = Precision::deserialize("Double".into_deserializer());
+    assert_eq!(precision, Ok::<_, Error>(Precision::Double));
+    let layout = Layout::deserialize("Diagonal".into_deserializer());
+    assert!(layout.map_err(|_: Error| ()).is_err());
+  }
+}
@@ -5,12 +5,11 @@ use ocl::ProQue;
 #[cfg(test)]
 mod tests {
   use clblast_binding::{
-    self,
+    self, Ld, Offset,
     clblast_sys::{self, CLBlastLayout, CLBlastTranspose},
     sgemm,
   };
-  // ProQueの代わりに、必要なoclコンポーネントを直接インポート
-  use ocl::{Buffer, Context, Device, Platform, ProQue, Queue};
+  use ocl::{Buffer, ProQue};
 
   fn gemm_cpu_ref(m: usize, n: usize, k: usize, a: &[f32], b: &[f32]) -> Vec<f32> {
     let mut c = vec![0f32; m * n];
@@ -28,13 +27,7 @@ mod tests {
 
   #[test]
   fn sgemm_small_with_clblast_and_ocl() -> ocl::Result<()> {
-    let platform = Platform::default();
-    let device = Device::first(platform)?;
-    let context = Context::builder()
-      .platform(platform)
-      .devices(device)
-      .build()?;
-    let queue = Queue::new(&context, device, None)?;
+    let queue = clblast_binding::default_queue()?;
 
     let (m, n, k) = (2usize, 3usize, 4usize);
     let a_host: Vec<f32> = (0..(m * k)).map(|i| i as f32).collect();
@@ -64,15 +57,15 @@ mod tests {
       k,
       1.0,
       &a_buffer,
-      0usize,
-      k,
+      Offset(0),
+      Ld(k),
       &b_buffer,
-      0usize,
-      n,
+      Offset(0),
+      Ld(n),
       0.0,
       &c_buffer,
-      0usize,
-      n,
+      Offset(0),
+      Ld(n),
       &[],
     );
 
@@ -121,15 +114,15 @@ mod tests {
       k,
       1.0,
       &a,
-      0,
-      k,
+      Offset(0),
+      Ld(k),
       &b,
-      0,
-      n,
+      Offset(0),
+      Ld(n),
       0.0,
       &c,
-      0,
-      n,
+      Offset(0),
+      Ld(n),
       &[],
     )?;
     Ok(())
@@ -0,0 +1,33 @@
+#[cfg(all(test, feature = "nalgebra"))]
+mod tests {
+  use clblast_binding::error::CLBlastError;
+  use clblast_binding::{default_queue, interop_nalgebra::gemm_nalgebra};
+  use nalgebra::DMatrix;
+
+  #[test]
+  fn gemm_nalgebra_matches_cpu_product() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let a = DMatrix::from_fn(3, 4, |i, j| (i * 4 + j) as f32);
+    let b = DMatrix::from_fn(4, 2, |i, j| i as f32 - 2.0 * j as f32);
+
+    let got = gemm_nalgebra(&queue, &a, &b)?;
+    let want = &a * &b;
+    assert_eq!(got.shape(), want.shape());
+    for (x, y) in got.iter().zip(want.iter()) {
+      assert!((x - y).abs() < 1e-4, "got {x}, expect {y}");
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn gemm_nalgebra_rejects_mismatched_inner_dimension() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let a = DMatrix::<f32>::zeros(3, 4);
+    let b = DMatrix::<f32>::zeros(5, 2);
+    assert!(matches!(
+      gemm_nalgebra(&queue, &a, &b),
+      Err(CLBlastError::InvalidDimension)
+    ));
+    Ok(())
+  }
+}
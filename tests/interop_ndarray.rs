@@ -0,0 +1,56 @@
+#[cfg(all(test, feature = "ndarray"))]
+mod tests {
+  use clblast_binding::{
+    default_queue,
+    interop_ndarray::{gemm_ndarray, sgemm_host},
+  };
+  use ndarray::{Array2, ArrayView2, s};
+
+  fn assert_close(got: &Array2<f32>, want: &Array2<f32>) {
+    assert_eq!(got.dim(), want.dim());
+    for (x, y) in got.iter().zip(want.iter()) {
+      assert!((x - y).abs() < 1e-3, "got {x}, expect {y}");
+    }
+  }
+
+  #[test]
+  fn sgemm_host_matches_ndarray_dot() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let a = Array2::from_shape_fn((3, 4), |(i, j)| (i * 4 + j) as f32);
+    // Transposed view: a column-major `b`, passed through the transpose
+    // flag rather than packed.
+    let b = Array2::from_shape_fn((2, 4), |(i, j)| (i as f32) - (j as f32));
+    let b = b.t();
+
+    let got = sgemm_host(&queue, a.view(), b)?;
+    assert_close(&got, &a.dot(&b));
+    Ok(())
+  }
+
+  #[test]
+  fn gemm_ndarray_matches_dot_for_every_memory_order() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let a = Array2::from_shape_fn((5, 6), |(i, j)| (i * 6 + j) as f32 * 0.25);
+    let b = Array2::from_shape_fn((6, 3), |(i, j)| i as f32 - 2.0 * j as f32);
+    let at = Array2::from_shape_fn((6, 5), |(i, j)| a[(j, i)]);
+    let bt = Array2::from_shape_fn((3, 6), |(i, j)| b[(j, i)]);
+    // Every other column of a 5 x 12 array: neither row- nor column-major.
+    let wide = Array2::from_shape_fn(
+      (5, 12),
+      |(i, j)| if j % 2 == 0 { a[(i, j / 2)] } else { -1.0 },
+    );
+    let strided = wide.slice(s![.., ..;2]);
+
+    let views: [(ArrayView2<f32>, ArrayView2<f32>); 4] = [
+      (a.view(), b.view()),
+      (at.t(), b.view()),
+      (a.view(), bt.t()),
+      (strided, bt.t()),
+    ];
+    let want = a.dot(&b);
+    for (a, b) in views {
+      assert_close(&gemm_ndarray(&queue, a, b)?, &want);
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,434 @@
+#[cfg(all(test, feature = "level1"))]
+mod tests {
+  use clblast_binding::{CLBlastError, Offset, default_queue, level1};
+  use ocl::Buffer;
+
+  #[test]
+  fn sdot_scalar_matches_host() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = [1.0f32, 2.0, 3.0];
+    let y = [4.0f32, -5.0, 6.0];
+    let xb = Buffer::builder()
+      .queue(queue.clone())
+      .len(x.len())
+      .copy_host_slice(&x)
+      .build()?;
+    let yb = Buffer::builder()
+      .queue(queue.clone())
+      .len(y.len())
+      .copy_host_slice(&y)
+      .build()?;
+
+    let dot = level1::sdot_scalar(&queue, 3, &xb, 0, 1, &yb, 0, 1)?;
+    assert!((dot - 12.0).abs() < 1e-5, "dot = {dot}");
+    Ok(())
+  }
+
+  #[test]
+  fn vector_reductions_match_host() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = [3.0f32, -4.0, 1.5, -2.0, 0.5];
+    let xb = Buffer::builder()
+      .queue(queue.clone())
+      .len(x.len())
+      .copy_host_slice(&x)
+      .build()?;
+
+    let nrm2 = x.iter().map(|v| v * v).sum::<f32>().sqrt();
+    let asum = x.iter().map(|v| v.abs()).sum::<f32>();
+    let sum = x.iter().sum::<f32>();
+    let got = level1::snrm2_scalar(&queue, 5, &xb, 0, 1)?;
+    assert!((got - nrm2).abs() < 1e-4, "nrm2 = {got}");
+    let got = level1::sasum_scalar(&queue, 5, &xb, 0, 1)?;
+    assert!((got - asum).abs() < 1e-4, "asum = {got}");
+    let got = level1::ssum_scalar(&queue, 5, &xb, 0, 1)?;
+    assert!((got - sum).abs() < 1e-4, "sum = {got}");
+
+    // Every other element, starting at the second: [-4.0, -2.0].
+    let got = level1::sasum_scalar(&queue, 2, &xb, 1, 2)?;
+    assert!((got - 6.0).abs() < 1e-5, "strided asum = {got}");
+    assert_eq!(level1::ssum_scalar(&queue, 0, &xb, 0, 1)?, 0.0);
+    Ok(())
+  }
+
+  #[test]
+  fn index_reductions_match_host() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = [3.0f32, -7.0, 0.5, 6.0, -0.25, 2.0];
+    let xb = Buffer::builder()
+      .queue(queue.clone())
+      .len(x.len())
+      .copy_host_slice(&x)
+      .build()?;
+
+    assert_eq!(level1::isamax(&queue, 6, &xb, 0, 1)?, 1);
+    assert_eq!(level1::isamin(&queue, 6, &xb, 0, 1)?, 4);
+    assert_eq!(level1::ismax(&queue, 6, &xb, 0, 1)?, 3);
+    assert_eq!(level1::ismin(&queue, 6, &xb, 0, 1)?, 1);
+    // Every other element, starting at the first: [3.0, 0.5, -0.25].
+    assert_eq!(level1::isamax(&queue, 3, &xb, 0, 2)?, 0);
+    assert_eq!(level1::isamin(&queue, 3, &xb, 0, 2)?, 2);
+
+    let ties = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+    assert!(level1::isamax(&queue, 4, &ties, 0, 1)? < 4);
+
+    let empty = level1::isamax(&queue, 0, &xb, 0, 1);
+    assert!(matches!(empty, Err(CLBlastError::InvalidDimension)));
+    Ok(())
+  }
+
+  #[test]
+  fn wait_for_accepts_borrowed_events() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let filled = |v: f32| {
+      Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(4)
+        .fill_val(v)
+        .build()
+    };
+    let (x, y) = (filled(1.0)?, filled(2.0)?);
+
+    let x_scaled =
+      clblast_binding::sscal(&queue, 4, 3.0, &x, Offset(0), 1, &[])?.expect("no event");
+    let y_scaled =
+      clblast_binding::sscal(&queue, 4, 0.5, &y, Offset(0), 1, &[])?.expect("no event");
+    // y = x + y, waiting on events held in separate bindings.
+    let done = clblast_binding::saxpy(
+      &queue,
+      4,
+      1.0,
+      &x,
+      Offset(0),
+      1,
+      &y,
+      Offset(0),
+      1,
+      [&x_scaled, &y_scaled],
+    )?
+    .expect("no event");
+
+    let mut got = vec![0.0f32; 4];
+    y.read(&mut got).ewait(&done).enq()?;
+    assert_eq!(got, [4.0; 4]);
+    Ok(())
+  }
+
+  #[test]
+  fn wait_for_accepts_owned_event_collections() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+
+    let mut events = Vec::new();
+    events.extend(clblast_binding::sscal(
+      &queue,
+      4,
+      2.0,
+      &x,
+      Offset(0),
+      1,
+      &[],
+    )?);
+    // A `&Vec<CoreEvent>` (or a slice of one) is used without collecting.
+    events.extend(clblast_binding::sscal(
+      &queue,
+      4,
+      2.0,
+      &x,
+      Offset(0),
+      1,
+      &events,
+    )?);
+    let done = clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, events.iter().rev())?
+      .expect("no event");
+
+    let mut got = vec![0.0f32; 4];
+    x.read(&mut got).ewait(&done).enq()?;
+    assert_eq!(got, [8.0; 4]);
+    Ok(())
+  }
+
+  #[cfg(feature = "raw-api")]
+  #[test]
+  fn raw_queue_runs_routines() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // Stands in for a handle handed over by a C host; `queue` keeps it alive.
+    let raw = unsafe { clblast_binding::RawQueue::from_raw(queue.as_ptr()) };
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(3)
+      .fill_val(2.0)
+      .build()?;
+
+    level1::sscal(&raw, 3, 1.5, &x, 0, 1, &[])?;
+
+    let mut got = vec![0.0f32; 3];
+    x.read(&mut got).enq()?;
+    assert_eq!(got, [3.0; 3]);
+    Ok(())
+  }
+
+  #[cfg(feature = "raw-api")]
+  #[test]
+  fn raw_handles_run_generated_wrappers() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::{Offset, RawMem, RawQueue};
+
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(3)
+      .fill_val(2.0)
+      .build()?;
+    // Stand in for handles from another OpenCL binding; `queue` and `x`
+    // keep them alive.
+    let raw_queue = unsafe { RawQueue::from_raw(queue.as_ptr()) };
+    let raw_x = unsafe { RawMem::<f32>::from_raw(x.as_core().as_ptr()) };
+
+    clblast_binding::sscal(&raw_queue, 3, 1.5, &raw_x, Offset(0), 1, &[])?;
+    clblast_binding::sscal(&raw_queue, 3, 2.0, &x, Offset(0), 1, &[])?;
+    queue.finish()?;
+
+    let mut got = vec![0.0f32; 3];
+    x.read(&mut got).enq()?;
+    assert_eq!(got, [6.0; 3]);
+    Ok(())
+  }
+
+  #[test]
+  fn snormalize_returns_norm_and_scales() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let xb = Buffer::builder()
+      .queue(queue.clone())
+      .len(2)
+      .copy_host_slice(&[3.0f32, 4.0])
+      .build()?;
+    let norm = level1::snormalize(&queue, 2, &xb, 0, 1)?;
+    assert!((norm - 5.0).abs() < 1e-5, "norm = {norm}");
+
+    let mut got = vec![0.0f32; 2];
+    xb.read(&mut got).enq()?;
+    assert!(
+      (got[0] - 0.6).abs() < 1e-5 && (got[1] - 0.8).abs() < 1e-5,
+      "{got:?}"
+    );
+
+    // A zero vector is left as is.
+    let zb = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(2)
+      .fill_val(0.0)
+      .build()?;
+    assert_eq!(level1::snormalize(&queue, 2, &zb, 0, 1)?, 0.0);
+    zb.read(&mut got).enq()?;
+    assert_eq!(got, [0.0, 0.0]);
+    Ok(())
+  }
+
+  #[cfg(feature = "complex")]
+  #[test]
+  fn cdotu_and_cdotc_differ_by_conjugation() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::Complex32;
+
+    let queue = default_queue()?;
+    let x = [Complex32::new(1.0, 2.0), Complex32::new(3.0, -1.0)];
+    let y = [Complex32::new(2.0, -1.0), Complex32::new(1.0, 1.0)];
+    let xb = Buffer::builder()
+      .queue(queue.clone())
+      .len(x.len())
+      .copy_host_slice(&x)
+      .build()?;
+    let yb = Buffer::builder()
+      .queue(queue.clone())
+      .len(y.len())
+      .copy_host_slice(&y)
+      .build()?;
+
+    // dotu: (1+2i)(2-i) + (3-i)(1+i) = (4+3i) + (4+2i)
+    let u = level1::cdotu_scalar(&queue, 2, &xb, 0, 1, &yb, 0, 1)?;
+    assert!((u - Complex32::new(8.0, 5.0)).norm() < 1e-5, "dotu = {u}");
+    // dotc: (1-2i)(2-i) + (3+i)(1+i) = (-5i) + (2+4i)
+    let c = level1::cdotc_scalar(&queue, 2, &xb, 0, 1, &yb, 0, 1)?;
+    assert!((c - Complex32::new(2.0, -1.0)).norm() < 1e-5, "dotc = {c}");
+    Ok(())
+  }
+
+  #[cfg(feature = "blocking-wrappers")]
+  #[test]
+  fn blocking_wrapper_completes_before_returning() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(3)
+      .fill_val(1.5)
+      .build()?;
+    clblast_binding::sscal_blocking(&queue, 3, 2.0, &x, Offset(0), 1, &[])?;
+    let mut got = vec![0.0f32; 3];
+    x.read(&mut got).enq()?;
+    assert_eq!(got, [3.0; 3]);
+    Ok(())
+  }
+
+  #[test]
+  fn chained_normalize_axpy_copy_swap_pipeline() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let buffer = |host: &[f32]| {
+      Buffer::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    let (x, y) = (buffer(&[3.0, 4.0])?, buffer(&[1.0, 1.0])?);
+    let (z, w) = (buffer(&[0.0, 0.0])?, buffer(&[9.0, 9.0])?);
+
+    // x / |x| = [0.6, 0.8]; y = 2 * x + y = [2.2, 2.6]; z = y; z <-> w.
+    let x = level1::sscal_chain(&queue, 2, 1.0 / 5.0, &x, 0, 1)?;
+    let y = level1::saxpy_chain(&queue, 2, 2.0, x, 0, 1, &y, 0, 1)?;
+    let z = level1::scopy_chain(&queue, 2, y, 0, 1, &z, 0, 1)?;
+    let (z, w) = level1::sswap_chain(&queue, 2, z, 0, 1, &w, 0, 1)?;
+
+    let mut got = vec![0.0f32; 2];
+    w.read(&mut got).enq()?;
+    assert!(
+      (got[0] - 2.2).abs() < 1e-5 && (got[1] - 2.6).abs() < 1e-5,
+      "w = {got:?}"
+    );
+    z.read(&mut got).enq()?;
+    assert_eq!(got, [9.0, 9.0]);
+    Ok(())
+  }
+
+  #[test]
+  fn strided_overruns_are_rejected_before_enqueueing() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // Seven elements: `n = 4, inc = 2` fits exactly from offset 0 (last
+    // index 6) and overruns by one from offset 1.
+    let x_host: Vec<f32> = (0..7).map(|i| i as f32).collect();
+    let y_host: Vec<f32> = (0..7).map(|i| -(i as f32)).collect();
+    let buffer = |host: &[f32]| {
+      Buffer::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    let (x, y) = (buffer(&x_host)?, buffer(&y_host)?);
+    let overrun = |r: Result<Option<ocl::core::Event>, CLBlastError>| {
+      matches!(r, Err(CLBlastError::InvalidBufferSize))
+    };
+
+    assert!(overrun(level1::sscal(&queue, 4, 2.0, &x, 1, 2, &[])));
+    assert!(overrun(level1::sscal(&queue, 8, 2.0, &x, 0, 1, &[])));
+    assert!(overrun(level1::scopy(&queue, 4, &x, 0, 2, &y, 1, 2, &[])));
+    assert!(overrun(level1::scopy(&queue, 3, &x, 0, 3, &y, 0, 4, &[])));
+    assert!(overrun(level1::sswap(&queue, 4, &x, 1, 2, &y, 0, 2, &[])));
+    assert!(overrun(level1::sswap(&queue, 7, &x, 0, 1, &y, 1, 1, &[])));
+    assert!(overrun(level1::saxpy(
+      &queue,
+      4,
+      1.0,
+      &x,
+      1,
+      2,
+      &y,
+      0,
+      2,
+      &[]
+    )));
+    assert!(overrun(level1::saxpy(
+      &queue,
+      2,
+      1.0,
+      &x,
+      0,
+      6,
+      &y,
+      0,
+      7,
+      &[]
+    )));
+
+    // Nothing was enqueued: both buffers still hold their initial values.
+    queue.finish()?;
+    let mut got = vec![0.0f32; 7];
+    x.read(&mut got).enq()?;
+    assert_eq!(got, x_host);
+    y.read(&mut got).enq()?;
+    assert_eq!(got, y_host);
+
+    // The exact fit is accepted.
+    let event = level1::sscal(&queue, 4, 2.0, &x, 0, 2, &[])?.expect("no event");
+    x.read(&mut got).ewait(&event).enq()?;
+    assert_eq!(got, [0.0, 1.0, 4.0, 3.0, 8.0, 5.0, 12.0]);
+    Ok(())
+  }
+
+  #[cfg(feature = "batched")]
+  #[test]
+  fn saxpy_batched_matches_host() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // Three axpys of length 4 over disjoint slices of one x and one y.
+    let (n, alphas) = (4usize, [2.0f32, -1.0, 0.5]);
+    let (x_offsets, y_offsets) = ([0usize, 4, 8], [8usize, 0, 4]);
+    let x: Vec<f32> = (0..12).map(|i| i as f32).collect();
+    let y: Vec<f32> = (0..12).map(|i| 100.0 - i as f32).collect();
+    let buffer = |host: &[f32]| {
+      Buffer::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    let (xb, yb) = (buffer(&x)?, buffer(&y)?);
+
+    let event = level1::saxpy_batched(
+      &queue,
+      n,
+      &alphas,
+      &xb,
+      &x_offsets,
+      1,
+      &yb,
+      &y_offsets,
+      1,
+      &[],
+    )?
+    .expect("no event");
+    let mut got = vec![0.0f32; 12];
+    yb.read(&mut got).ewait(&event).enq()?;
+
+    let mut want = y.clone();
+    for b in 0..alphas.len() {
+      for i in 0..n {
+        want[y_offsets[b] + i] += alphas[b] * x[x_offsets[b] + i];
+      }
+    }
+    assert_eq!(got, want);
+
+    let mismatched = level1::saxpy_batched(
+      &queue,
+      n,
+      &alphas,
+      &xb,
+      &x_offsets[..2],
+      1,
+      &yb,
+      &y_offsets,
+      1,
+      &[],
+    );
+    assert!(matches!(
+      mismatched,
+      Err(clblast_binding::CLBlastError::InvalidBatchCount)
+    ));
+    Ok(())
+  }
+}
@@ -0,0 +1,298 @@
+#[cfg(all(test, feature = "level2"))]
+mod tests {
+  use clblast_binding::{
+    CLBlastError,
+    clblast_sys::{CLBlastLayout, CLBlastTranspose},
+    default_queue, level2,
+  };
+  use ocl::{Buffer, Queue};
+
+  fn buffer<T: ocl::OclPrm>(queue: &Queue, host: &[T]) -> ocl::Result<Buffer<T>> {
+    Buffer::builder()
+      .queue(queue.clone())
+      .len(host.len())
+      .copy_host_slice(host)
+      .build()
+  }
+
+  #[test]
+  fn sger_matches_hand_computed_update() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // A = [[1, 0, 0], [0, 1, 0]] (row-major), x = [1, 2], y = [3, 4, 5]:
+    // A + 2 * x * y^T = [[1 + 6, 8, 10], [12, 1 + 16, 20]].
+    let x = buffer(&queue, &[1.0f32, 2.0])?;
+    let y = buffer(&queue, &[3.0f32, 4.0, 5.0])?;
+    let a = buffer(&queue, &[1.0f32, 0.0, 0.0, 0.0, 1.0, 0.0])?;
+
+    let event = level2::sger(
+      &queue,
+      CLBlastLayout::RowMajor,
+      2,
+      3,
+      2.0,
+      &x,
+      0,
+      1,
+      &y,
+      0,
+      1,
+      &a,
+      0,
+      3,
+      &[],
+    )?
+    .expect("no event");
+    let mut got = vec![0.0f32; 6];
+    a.read(&mut got).ewait(&event).enq()?;
+    assert_eq!(got, [7.0, 8.0, 10.0, 12.0, 17.0, 20.0]);
+    Ok(())
+  }
+
+  #[cfg(feature = "complex")]
+  #[test]
+  fn cgeru_and_cgerc_differ_by_conjugation() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::Complex32;
+
+    let queue = default_queue()?;
+    let x = [Complex32::new(1.0, 2.0)];
+    let y = [Complex32::new(3.0, 4.0), Complex32::new(0.0, 1.0)];
+    let zero = [Complex32::new(0.0, 0.0); 2];
+    let one = Complex32::new(1.0, 0.0);
+    let (xb, yb) = (buffer(&queue, &x)?, buffer(&queue, &y)?);
+
+    let mut got = vec![zero[0]; 2];
+    // geru: (1+2i)(3+4i) = -5+10i, (1+2i)(i) = -2+i
+    let a = buffer(&queue, &zero)?;
+    level2::cgeru(
+      &queue,
+      CLBlastLayout::RowMajor,
+      1,
+      2,
+      one,
+      &xb,
+      0,
+      1,
+      &yb,
+      0,
+      1,
+      &a,
+      0,
+      2,
+      &[],
+    )?;
+    a.read(&mut got).enq()?;
+    assert!(
+      (got[0] - Complex32::new(-5.0, 10.0)).norm() < 1e-5,
+      "geru = {got:?}"
+    );
+    assert!(
+      (got[1] - Complex32::new(-2.0, 1.0)).norm() < 1e-5,
+      "geru = {got:?}"
+    );
+
+    // gerc: (1+2i)(3-4i) = 11+2i, (1+2i)(-i) = 2-i
+    let a = buffer(&queue, &zero)?;
+    level2::cgerc(
+      &queue,
+      CLBlastLayout::RowMajor,
+      1,
+      2,
+      one,
+      &xb,
+      0,
+      1,
+      &yb,
+      0,
+      1,
+      &a,
+      0,
+      2,
+      &[],
+    )?;
+    a.read(&mut got).enq()?;
+    assert!(
+      (got[0] - Complex32::new(11.0, 2.0)).norm() < 1e-5,
+      "gerc = {got:?}"
+    );
+    assert!(
+      (got[1] - Complex32::new(2.0, -1.0)).norm() < 1e-5,
+      "gerc = {got:?}"
+    );
+    Ok(())
+  }
+
+  #[test]
+  fn sger_rejects_short_operands() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n) = (2usize, 3usize);
+    let ger = |x: &Buffer<f32>, y: &Buffer<f32>, a: &Buffer<f32>| {
+      level2::sger(
+        &queue,
+        CLBlastLayout::RowMajor,
+        m,
+        n,
+        1.0,
+        x,
+        0,
+        1,
+        y,
+        0,
+        1,
+        a,
+        0,
+        n,
+        &[],
+      )
+    };
+    let (x, y, a) = (
+      buffer(&queue, &[0.0f32; 2])?,
+      buffer(&queue, &[0.0f32; 3])?,
+      buffer(&queue, &[0.0f32; 6])?,
+    );
+    let (short_x, short_y, short_a) = (
+      buffer(&queue, &[0.0f32; 1])?,
+      buffer(&queue, &[0.0f32; 2])?,
+      buffer(&queue, &[0.0f32; 5])?,
+    );
+
+    assert!(matches!(
+      ger(&short_x, &y, &a),
+      Err(CLBlastError::InvalidBufferSize)
+    ));
+    assert!(matches!(
+      ger(&x, &short_y, &a),
+      Err(CLBlastError::InvalidBufferSize)
+    ));
+    assert!(matches!(
+      ger(&x, &y, &short_a),
+      Err(CLBlastError::InvalidBufferSize)
+    ));
+    assert!(ger(&x, &y, &a)?.is_some());
+    queue.finish()?;
+    Ok(())
+  }
+
+  /// Dense `m x n` rows with `kl` sub- and `ku` super-diagonals, in the
+  /// banded storage of `layout` (`a_ld == kl + ku + 1`).
+  fn banded(layout: CLBlastLayout, dense: &[Vec<f32>], kl: usize, ku: usize) -> Vec<f32> {
+    let ld = kl + ku + 1;
+    let count = match layout {
+      CLBlastLayout::RowMajor => dense.len(),
+      CLBlastLayout::ColMajor => dense[0].len(),
+    };
+    let mut a = vec![0.0f32; ld * count];
+    for (i, row) in dense.iter().enumerate() {
+      for (j, &value) in row.iter().enumerate() {
+        if i > j + kl || j > i + ku {
+          continue;
+        }
+        let at = match layout {
+          CLBlastLayout::RowMajor => i * ld + kl + j - i,
+          CLBlastLayout::ColMajor => j * ld + ku + i - j,
+        };
+        a[at] = value;
+      }
+    }
+    a
+  }
+
+  #[test]
+  fn sgbmv_matches_dense_product_in_both_layouts() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n, kl, ku) = (4usize, 5usize, 1usize, 2usize);
+    let dense: Vec<Vec<f32>> = (0..m)
+      .map(|i| {
+        (0..n)
+          .map(|j| {
+            if i > j + kl || j > i + ku {
+              0.0
+            } else {
+              (10 * i + j + 1) as f32
+            }
+          })
+          .collect()
+      })
+      .collect();
+    let x = [1.0f32, -2.0, 3.0, 0.5, 2.0];
+    let want: Vec<f32> = dense
+      .iter()
+      .map(|row| row.iter().zip(&x).map(|(a, x)| a * x).sum())
+      .collect();
+    let xb = buffer(&queue, &x)?;
+
+    for layout in [CLBlastLayout::RowMajor, CLBlastLayout::ColMajor] {
+      let a = buffer(&queue, &banded(layout, &dense, kl, ku))?;
+      let y = buffer(&queue, &[0.0f32; 4])?;
+      level2::sgbmv(
+        &queue,
+        layout,
+        CLBlastTranspose::No,
+        m,
+        n,
+        kl,
+        ku,
+        1.0,
+        &a,
+        0,
+        kl + ku + 1,
+        &xb,
+        0,
+        1,
+        0.0,
+        &y,
+        0,
+        1,
+        &[],
+      )?;
+      let mut got = vec![0.0f32; m];
+      y.read(&mut got).enq()?;
+      for i in 0..m {
+        assert!(
+          (got[i] - want[i]).abs() < 1e-3,
+          "{layout:?} y[{i}]: got {}, expect {}",
+          got[i],
+          want[i]
+        );
+      }
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn sgbmv_rejects_lead_dim_below_band() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n, kl, ku) = (4usize, 5usize, 1usize, 2usize);
+    let a = buffer(&queue, &[0.0f32; 64])?;
+    let x = buffer(&queue, &[0.0f32; 5])?;
+    let y = buffer(&queue, &[0.0f32; 4])?;
+
+    for layout in [CLBlastLayout::RowMajor, CLBlastLayout::ColMajor] {
+      let result = level2::sgbmv(
+        &queue,
+        layout,
+        CLBlastTranspose::No,
+        m,
+        n,
+        kl,
+        ku,
+        1.0,
+        &a,
+        0,
+        kl + ku,
+        &x,
+        0,
+        1,
+        0.0,
+        &y,
+        0,
+        1,
+        &[],
+      );
+      assert!(
+        matches!(result, Err(CLBlastError::InvalidLeadDimA)),
+        "{layout:?}"
+      );
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,1334 @@
+#[cfg(all(test, feature = "level3"))]
+mod tests {
+  use clblast_binding::{
+    CLBlastError, ClblastFloat, ClblastScalar, Ld, Offset,
+    builder::GemmBuilder,
+    caps,
+    clblast_sys::{
+      CLBlastLayout, CLBlastPrecision, CLBlastSide, CLBlastTranspose, CLBlastTriangle,
+    },
+    default_queue,
+    enums::Transpose,
+    level3, sgemm,
+  };
+  use ocl::prm::Float2;
+  use ocl::{Buffer, Queue};
+
+  #[test]
+  fn ssymm_matches_full_gemm() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n) = (3usize, 2usize);
+
+    // Symmetric 3x3 matrix, row-major.
+    let full: Vec<f32> = vec![
+      4.0, 1.0, 2.0, //
+      1.0, 5.0, 3.0, //
+      2.0, 3.0, 6.0,
+    ];
+    // Same matrix with the strictly-lower triangle overwritten: ssymm with
+    // `Triangle::Upper` must not read it.
+    let mut upper_only = full.clone();
+    for i in 0..m {
+      for j in 0..i {
+        upper_only[i * m + j] = -999.0;
+      }
+    }
+    let b_host: Vec<f32> = (0..(m * n)).map(|i| i as f32 + 1.0).collect();
+
+    let a_full = Buffer::builder()
+      .queue(queue.clone())
+      .len(m * m)
+      .copy_host_slice(&full)
+      .build()?;
+    let a_upper = Buffer::builder()
+      .queue(queue.clone())
+      .len(m * m)
+      .copy_host_slice(&upper_only)
+      .build()?;
+    let b = Buffer::builder()
+      .queue(queue.clone())
+      .len(m * n)
+      .copy_host_slice(&b_host)
+      .build()?;
+    let c_symm = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(m * n)
+      .fill_val(0.0)
+      .build()?;
+    let c_gemm = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(m * n)
+      .fill_val(0.0)
+      .build()?;
+
+    level3::ssymm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastSide::Left,
+      CLBlastTriangle::Upper,
+      m,
+      n,
+      1.0,
+      &a_upper,
+      0,
+      m,
+      &b,
+      0,
+      n,
+      0.0,
+      &c_symm,
+      0,
+      n,
+      &[],
+    )?;
+    sgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      m,
+      n,
+      m,
+      1.0,
+      &a_full,
+      Offset(0),
+      Ld(m),
+      &b,
+      Offset(0),
+      Ld(n),
+      0.0,
+      &c_gemm,
+      Offset(0),
+      Ld(n),
+      &[],
+    )?;
+
+    let mut got = vec![0.0f32; m * n];
+    let mut want = vec![0.0f32; m * n];
+    c_symm.read(&mut got).enq()?;
+    c_gemm.read(&mut want).enq()?;
+    for (i, (&x, &y)) in got.iter().zip(want.iter()).enumerate() {
+      assert!((x - y).abs() < 1e-4, "mismatch at {i}: got {x}, expect {y}");
+    }
+    Ok(())
+  }
+
+  fn filled(queue: &Queue, host: &[f32]) -> ocl::Result<Buffer<f32>> {
+    Buffer::builder()
+      .queue(queue.clone())
+      .len(host.len())
+      .copy_host_slice(host)
+      .build()
+  }
+
+  #[test]
+  fn sgemm_with_empty_output_is_noop() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let a = filled(&queue, &[1.0; 4])?;
+    let b = filled(&queue, &[1.0; 4])?;
+    let c_host = [7.0f32; 4];
+    let c = filled(&queue, &c_host)?;
+
+    // m == 0 and n == 0: nothing is enqueued and C is untouched.
+    for (m, n) in [(0usize, 2usize), (2, 0)] {
+      let event = level3::sgemm(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        m,
+        n,
+        2,
+        1.0,
+        &a,
+        0,
+        2,
+        &b,
+        0,
+        n.max(1),
+        0.0,
+        &c,
+        0,
+        n.max(1),
+        &[],
+      )?;
+      assert!(event.is_none(), "m={m}, n={n} enqueued work");
+    }
+
+    let mut got = vec![0.0f32; 4];
+    c.read(&mut got).enq()?;
+    assert_eq!(got, c_host);
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_with_k_zero_scales_c_by_beta() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n, ldc) = (2usize, 3usize, 4usize);
+    let a = filled(&queue, &[0.0; 1])?;
+    let b = filled(&queue, &[0.0; 1])?;
+    // Padded rows: the last column of each row must not be scaled.
+    let c_host: Vec<f32> = (0..(m * ldc)).map(|i| i as f32).collect();
+    let c = filled(&queue, &c_host)?;
+
+    level3::sgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      m,
+      n,
+      0,
+      1.0,
+      &a,
+      0,
+      1,
+      &b,
+      0,
+      n,
+      0.5,
+      &c,
+      0,
+      ldc,
+      &[],
+    )?;
+
+    let mut got = vec![0.0f32; m * ldc];
+    c.read(&mut got).enq()?;
+    for (i, (&x, &orig)) in got.iter().zip(c_host.iter()).enumerate() {
+      let want = if i % ldc < n { 0.5 * orig } else { orig };
+      assert!(
+        (x - want).abs() < 1e-6,
+        "mismatch at {i}: got {x}, expect {want}"
+      );
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn k_zero_with_zero_beta_clears_nan() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (n, ldc) = (3usize, 4usize);
+    let a = filled(&queue, &[0.0; 1])?;
+    let b = filled(&queue, &[0.0; 1])?;
+    // NaN * 0 is NaN, so `beta == 0` has to overwrite C, padding excepted.
+    let c = filled(&queue, &[f32::NAN; 12])?;
+    level3::sgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      n,
+      n,
+      0,
+      1.0,
+      &a,
+      0,
+      1,
+      &b,
+      0,
+      n,
+      0.0,
+      &c,
+      0,
+      ldc,
+      &[],
+    )?;
+    let mut got = vec![0.0f32; n * ldc];
+    c.read(&mut got).enq()?;
+    for (i, x) in got.iter().enumerate() {
+      if i % ldc < n {
+        assert_eq!(*x, 0.0, "sgemm left {x} at {i}");
+      } else {
+        assert!(x.is_nan(), "sgemm wrote padding at {i}");
+      }
+    }
+
+    // `syrk` clears only the upper triangle.
+    let c = filled(&queue, &[f32::NAN; 12])?;
+    level3::ssyrk(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTriangle::Upper,
+      CLBlastTranspose::No,
+      n,
+      0,
+      1.0,
+      &a,
+      0,
+      1,
+      0.0,
+      &c,
+      0,
+      ldc,
+      &[],
+    )?;
+    c.read(&mut got).enq()?;
+    for (i, x) in got.iter().enumerate() {
+      let (row, col) = (i / ldc, i % ldc);
+      if col < n && col >= row {
+        assert_eq!(*x, 0.0, "ssyrk left {x} at ({row}, {col})");
+      } else {
+        assert!(
+          x.is_nan(),
+          "ssyrk wrote outside the triangle at ({row}, {col})"
+        );
+      }
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "alias-check")]
+  #[test]
+  fn strmm_rejects_aliased_a_and_b() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::{
+      CLBlastError,
+      clblast_sys::{CLBlastDiagonal, CLBlastSide},
+    };
+
+    let queue = default_queue()?;
+    let ab = filled(&queue, &[1.0; 4])?;
+    let result = level3::strmm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastSide::Left,
+      CLBlastTriangle::Upper,
+      CLBlastTranspose::No,
+      CLBlastDiagonal::NonUnit,
+      2,
+      2,
+      1.0,
+      &ab,
+      0,
+      2,
+      &ab,
+      0,
+      2,
+      &[],
+    );
+    assert!(matches!(result, Err(CLBlastError::AliasingViolation)));
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_accumulates_into_c_with_beta() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n, k) = (2usize, 3usize, 2usize);
+    let a_host = [1.0f32, 2.0, 3.0, 4.0];
+    let b_host = [1.0f32, 0.0, -1.0, 2.0, 1.0, 0.5];
+    let c_init = [10.0f32, 20.0, 30.0, 40.0, 50.0, 60.0];
+    let (alpha, beta) = (2.0f32, 0.5f32);
+    let a = filled(&queue, &a_host)?;
+    let b = filled(&queue, &b_host)?;
+    let c = filled(&queue, &c_init)?;
+
+    level3::sgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      m,
+      n,
+      k,
+      alpha,
+      &a,
+      0,
+      k,
+      &b,
+      0,
+      n,
+      beta,
+      &c,
+      0,
+      n,
+      &[],
+    )?;
+
+    let mut got = vec![0.0f32; m * n];
+    c.read(&mut got).enq()?;
+    for i in 0..m {
+      for j in 0..n {
+        let ab: f32 = (0..k).map(|p| a_host[i * k + p] * b_host[p * n + j]).sum();
+        let want = alpha * ab + beta * c_init[i * n + j];
+        let x = got[i * n + j];
+        assert!((x - want).abs() < 1e-4, "({i},{j}): got {x}, expect {want}");
+      }
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_event_orders_dependent_sgemm() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let n = 2usize;
+    let a_host = [1.0f32, 2.0, 3.0, 4.0];
+    let b_host = [0.5f32, -1.0, 2.0, 1.5];
+    let a = filled(&queue, &a_host)?;
+    let b = filled(&queue, &b_host)?;
+    let ab = filled(&queue, &[0.0; 4])?;
+    let abb = filled(&queue, &[0.0; 4])?;
+
+    let gemm = |x: &Buffer<f32>, c: &Buffer<f32>, wait_for: &[_]| {
+      level3::sgemm(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        n,
+        n,
+        n,
+        1.0,
+        x,
+        0,
+        n,
+        &b,
+        0,
+        n,
+        0.0,
+        c,
+        0,
+        n,
+        wait_for,
+      )
+    };
+    // (A * B) * B, the second product reading the first's output.
+    let first = gemm(&a, &ab, &[])?.expect("sgemm returned no event");
+    let second = gemm(&ab, &abb, &[first])?.expect("sgemm returned no event");
+
+    let mut got = vec![0.0f32; n * n];
+    abb.read(&mut got).ewait(&second).enq()?;
+    let mul = |x: &[f32], y: &[f32]| -> Vec<f32> {
+      (0..n * n)
+        .map(|ij| (0..n).map(|p| x[ij / n * n + p] * y[p * n + ij % n]).sum())
+        .collect()
+    };
+    let want = mul(&mul(&a_host, &b_host), &b_host);
+    for (i, (x, w)) in got.iter().zip(&want).enumerate() {
+      assert!((x - w).abs() < 1e-4, "[{i}]: got {x}, expect {w}");
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "batched")]
+  #[test]
+  fn sgemm_strided_batched_event_feeds_kernel() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (n, batch) = (2usize, 3usize);
+    let stride = n * n;
+    let a_host: Vec<f32> = (0..batch * stride).map(|i| i as f32).collect();
+    let b_host: Vec<f32> = (0..batch * stride).map(|i| 1.0 - i as f32 * 0.25).collect();
+    let a = filled(&queue, &a_host)?;
+    let b = filled(&queue, &b_host)?;
+    let c = filled(&queue, &vec![0.0; batch * stride])?;
+
+    let event = level3::sgemm_strided_batched(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      n,
+      n,
+      n,
+      1.0,
+      &a,
+      0,
+      n,
+      stride,
+      &b,
+      0,
+      n,
+      stride,
+      0.0,
+      &c,
+      0,
+      n,
+      stride,
+      batch,
+      &[],
+    )?
+    .expect("sgemm_strided_batched returned no event");
+
+    // A follow-up kernel ordered only by the returned event.
+    use ocl::{Event, Kernel, Program};
+    let program = Program::builder()
+      .src("__kernel void add_one(__global float* c) { c[get_global_id(0)] += 1.0f; }")
+      .devices(queue.device())
+      .build(&queue.context())?;
+    let kernel = Kernel::builder()
+      .program(&program)
+      .name("add_one")
+      .queue(queue.clone())
+      .global_work_size(batch * stride)
+      .arg(&c)
+      .build()?;
+    let mut done = Event::empty();
+    unsafe { kernel.cmd().ewait(&event).enew(&mut done).enq()? };
+
+    let mut got = vec![0.0f32; batch * stride];
+    c.read(&mut got).ewait(&done).enq()?;
+    for p in 0..batch {
+      for ij in 0..stride {
+        let (i, j) = (ij / n, ij % n);
+        let ab: f32 = (0..n)
+          .map(|q| a_host[p * stride + i * n + q] * b_host[p * stride + q * n + j])
+          .sum();
+        let x = got[p * stride + ij];
+        assert!(
+          (x - (ab + 1.0)).abs() < 1e-4,
+          "batch {p} ({i},{j}): got {x}, expect {}",
+          ab + 1.0
+        );
+      }
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "batched")]
+  #[test]
+  fn strided_batched_rejects_stride_past_buffer_end() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // Room for three 2x2 matrices, but a stride of 5 puts the third at 10..14.
+    let x = filled(&queue, &[1.0; 12])?;
+    let result = level3::sgemm_strided_batched(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      2,
+      2,
+      2,
+      1.0,
+      &x,
+      0,
+      2,
+      5,
+      &x,
+      0,
+      2,
+      4,
+      0.0,
+      &x,
+      0,
+      2,
+      4,
+      3,
+      &[],
+    );
+    assert!(matches!(result, Err(CLBlastError::InvalidDimension)));
+    Ok(())
+  }
+
+  #[cfg(feature = "batched")]
+  #[test]
+  fn sgemm_batched_uses_per_batch_offsets_and_scalars() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // Two 2x2 products of all-ones matrices, C stored in reverse order.
+    let a = filled(&queue, &[1.0; 8])?;
+    let c = filled(&queue, &[1.0; 8])?;
+    let (alphas, betas) = ([1.0f32, 3.0], [0.0f32, 1.0]);
+    let (offsets, c_offsets) = ([0usize, 4], [4usize, 0]);
+
+    let batched = |alphas: &[f32], batch_count| {
+      level3::sgemm_batched(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        2,
+        2,
+        2,
+        alphas,
+        &a,
+        &offsets,
+        2,
+        &a,
+        &offsets,
+        2,
+        &betas,
+        &c,
+        &c_offsets,
+        2,
+        batch_count,
+        &[],
+      )
+    };
+    assert!(matches!(
+      batched(&alphas[..1], 2),
+      Err(CLBlastError::InvalidBatchCount)
+    ));
+    let event = batched(&alphas, 2)?.expect("no event");
+
+    let mut got = vec![0.0f32; 8];
+    c.read(&mut got).ewait(&event).enq()?;
+    // Batch 1 (c[0..4]): 3 * 2 + 1; batch 0 (c[4..8]): 1 * 2 + 0.
+    assert_eq!(got, [7.0, 7.0, 7.0, 7.0, 2.0, 2.0, 2.0, 2.0]);
+    Ok(())
+  }
+
+  #[test]
+  #[ignore = "needs an fp64-capable device; run with --ignored"]
+  fn dgemm_matches_reference() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n, k) = (2usize, 3usize, 4usize);
+    let a_host: Vec<f64> = (0..m * k).map(|i| i as f64 * 0.5 - 1.0).collect();
+    let b_host: Vec<f64> = (0..k * n).map(|i| 1.0 / (i as f64 + 1.0)).collect();
+    let c_init: Vec<f64> = (0..m * n).map(|i| i as f64).collect();
+    let (alpha, beta) = (1.5f64, -0.25f64);
+    let filled = |host: &[f64]| {
+      Buffer::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    let a = filled(&a_host)?;
+    let b = filled(&b_host)?;
+    let c = filled(&c_init)?;
+
+    level3::dgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      m,
+      n,
+      k,
+      alpha,
+      &a,
+      0,
+      k,
+      &b,
+      0,
+      n,
+      beta,
+      &c,
+      0,
+      n,
+      &[],
+    )?;
+
+    let mut got = vec![0.0f64; m * n];
+    c.read(&mut got).enq()?;
+    for i in 0..m {
+      for j in 0..n {
+        let ab: f64 = (0..k).map(|p| a_host[i * k + p] * b_host[p * n + j]).sum();
+        let want = alpha * ab + beta * c_init[i * n + j];
+        let x = got[i * n + j];
+        assert!(
+          (x - want).abs() < 1e-12,
+          "({i},{j}): got {x}, expect {want}"
+        );
+      }
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn gemm_builder_matches_reference() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // A is 2x3, B is stored as 2x3 and used transposed, C = A * B^T is 2x2.
+    let a = filled(&queue, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0])?;
+    let b = filled(&queue, &[1.0, 0.0, 1.0, 0.0, 1.0, 0.0])?;
+    let c = filled(&queue, &[1.0; 4])?;
+
+    let event = GemmBuilder::new()
+      .dims(2, 2, 3)
+      .a(&a)
+      .b(&b)
+      .transpose_b(Transpose::Yes)
+      .c(&c)
+      .alpha(2.0)
+      .beta(1.0)
+      .run(&queue, &[])?
+      .expect("no event");
+    let mut got = vec![0.0f32; 4];
+    c.read(&mut got).ewait(&event).enq()?;
+    assert_eq!(got, [9.0, 5.0, 21.0, 11.0]);
+    Ok(())
+  }
+
+  #[test]
+  fn gemm_builder_rejects_missing_and_inconsistent_arguments()
+  -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let a = filled(&queue, &[0.0; 6])?;
+
+    let builder = GemmBuilder::new().dims(2, 2, 3).a(&a).b(&a).c(&a);
+    assert!(matches!(
+      builder.clone().lda(2).shape(),
+      Err(CLBlastError::InvalidDimension)
+    ));
+    assert!(matches!(
+      builder.clone().lda(2).run(&queue, &[]),
+      Err(CLBlastError::InvalidDimension)
+    ));
+    let shape = builder.transpose_b(Transpose::Yes).ldb(4).shape()?;
+    assert_eq!((shape.a_ld, shape.b_ld, shape.c_ld), (3, 4, 2));
+
+    let no_k = GemmBuilder::new().a(&a).b(&a).c(&a).m(2).n(2);
+    assert!(matches!(no_k.shape(), Err(CLBlastError::InvalidDimension)));
+    let no_c = GemmBuilder::new().dims(2, 2, 3).a(&a).b(&a);
+    assert!(matches!(
+      no_c.run(&queue, &[]),
+      Err(CLBlastError::InvalidMatrixC)
+    ));
+    Ok(())
+  }
+
+  /// `C = A * B` for `2 x 2` all-ones `A` and `B`, in the precision of `T`,
+  /// with `alpha`/`beta` from `T::Scalar`.
+  fn generic_ones_product<T: ClblastFloat>(
+    queue: &Queue,
+    one: T,
+    zero: T,
+  ) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    let buffer = |v: T| {
+      Buffer::<T>::builder()
+        .queue(queue.clone())
+        .len(4)
+        .fill_val(v)
+        .build()
+    };
+    let (a, c) = (buffer(one)?, buffer(zero)?);
+    let event = clblast_binding::gemm(
+      queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      2,
+      2,
+      2,
+      T::Scalar::ONE,
+      &a,
+      Offset(0),
+      Ld(2),
+      &a,
+      Offset(0),
+      Ld(2),
+      T::Scalar::ZERO,
+      &c,
+      Offset(0),
+      Ld(2),
+      &[],
+    )?
+    .expect("no event");
+    let mut got = vec![zero; 4];
+    c.read(&mut got).ewait(&event).enq()?;
+    Ok(got)
+  }
+
+  #[test]
+  fn generic_gemm_dispatches_on_element_type() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    assert_eq!(generic_ones_product(&queue, 1.0f32, 0.0)?, [2.0f32; 4]);
+    let product = generic_ones_product(&queue, Float2::new(1.0, 0.0), Float2::new(0.0, 0.0))?;
+    assert_eq!(product, [Float2::new(2.0, 0.0); 4]);
+    // As for `dgemm_matches_reference`, fp64 is optional.
+    if caps::supported_precisions(&queue.device())?.contains(&CLBlastPrecision::Double) {
+      assert_eq!(generic_ones_product(&queue, 1.0f64, 0.0)?, [2.0f64; 4]);
+    } else {
+      eprintln!("skipping f64: device has no fp64 support");
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "f16")]
+  #[test]
+  fn hgemm_matches_f32_reference() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::F16;
+    use half::f16;
+
+    let queue = default_queue()?;
+    if !caps::supported_precisions(&queue.device())?.contains(&CLBlastPrecision::Half) {
+      eprintln!("skipping: device has no fp16 support");
+      return Ok(());
+    }
+    let n = 8usize;
+    // Values exactly representable in f16, so only the arithmetic rounds.
+    let a_host: Vec<f32> = (0..n * n).map(|i| ((i % 7) as f32 - 3.0) * 0.25).collect();
+    let b_host: Vec<f32> = (0..n * n).map(|i| ((i % 5) as f32 - 2.0) * 0.5).collect();
+    let to_f16 = |host: &[f32]| {
+      host
+        .iter()
+        .map(|&x| F16(f16::from_f32(x)))
+        .collect::<Vec<_>>()
+    };
+    let buffer = |host: &[F16]| {
+      Buffer::<F16>::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    let a = buffer(&to_f16(&a_host))?;
+    let b = buffer(&to_f16(&b_host))?;
+    let c = buffer(&vec![F16::default(); n * n])?;
+
+    let event = clblast_binding::hgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      n,
+      n,
+      n,
+      f16::ONE,
+      &a,
+      Offset(0),
+      Ld(n),
+      &b,
+      Offset(0),
+      Ld(n),
+      f16::ZERO,
+      &c,
+      Offset(0),
+      Ld(n),
+      &[],
+    )?
+    .expect("no event");
+    let mut got = vec![F16::default(); n * n];
+    c.read(&mut got).ewait(&event).enq()?;
+
+    for i in 0..n {
+      for j in 0..n {
+        let want: f32 = (0..n).map(|q| a_host[i * n + q] * b_host[q * n + j]).sum();
+        let x = got[i * n + j].0.to_f32();
+        assert!(
+          (x - want).abs() <= 1e-2 * (1.0 + want.abs()),
+          "({i},{j}): got {x}, expect {want}"
+        );
+      }
+    }
+    Ok(())
+  }
+
+  #[cfg(feature = "complex")]
+  #[test]
+  fn gemm_builder_accepts_num_complex_elements() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::Complex32;
+
+    let queue = default_queue()?;
+    let buffer = |host: &[Complex32]| {
+      Buffer::<Complex32>::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    // A = i * I, so C = A * B = i * B with the default alpha = 1, beta = 0.
+    let i = Complex32::new(0.0, 1.0);
+    let zero = Complex32::new(0.0, 0.0);
+    let a = buffer(&[i, zero, zero, i])?;
+    let b_host = [
+      Complex32::new(1.0, 2.0),
+      Complex32::new(3.0, 0.0),
+      Complex32::new(0.0, -1.0),
+      Complex32::new(4.0, 4.0),
+    ];
+    let b = buffer(&b_host)?;
+    let c = buffer(&[zero; 4])?;
+
+    let event = GemmBuilder::new()
+      .dims(2, 2, 2)
+      .a(&a)
+      .b(&b)
+      .c(&c)
+      .run(&queue, &[])?
+      .expect("no event");
+    let mut got = vec![zero; 4];
+    c.read(&mut got).ewait(&event).enq()?;
+    let want: Vec<Complex32> = b_host.iter().map(|x| i * x).collect();
+    assert_eq!(got, want);
+    Ok(())
+  }
+
+  #[cfg(feature = "complex")]
+  #[test]
+  fn cgemm_conjugate_transpose_matches_host() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::Complex32;
+
+    let queue = default_queue()?;
+    let (m, n, k) = (2usize, 2usize, 3usize);
+    // A is stored k x m and used as A^H; B is k x n.
+    let a_host: Vec<Complex32> = (0..k * m)
+      .map(|i| Complex32::new(i as f32, 1.0 - i as f32))
+      .collect();
+    let b_host: Vec<Complex32> = (0..k * n)
+      .map(|i| Complex32::new(0.5 * i as f32, 2.0))
+      .collect();
+    let buffer = |host: &[Complex32]| {
+      Buffer::<Complex32>::builder()
+        .queue(queue.clone())
+        .len(host.len())
+        .copy_host_slice(host)
+        .build()
+    };
+    let (a, b) = (buffer(&a_host)?, buffer(&b_host)?);
+    let c = buffer(&vec![Complex32::new(1.0, 0.0); m * n])?;
+    let (alpha, beta) = (Complex32::new(0.0, 1.0), Complex32::new(2.0, 0.0));
+
+    let event = level3::cgemm(
+      &queue,
+      CLBlastLayout::RowMajor,
+      Transpose::Conjugate,
+      Transpose::No,
+      m,
+      n,
+      k,
+      alpha,
+      &a,
+      0,
+      m,
+      &b,
+      0,
+      n,
+      beta,
+      &c,
+      0,
+      n,
+      &[],
+    )?
+    .expect("no event");
+    let mut got = vec![Complex32::default(); m * n];
+    c.read(&mut got).ewait(&event).enq()?;
+
+    for i in 0..m {
+      for j in 0..n {
+        let ab: Complex32 = (0..k)
+          .map(|q| a_host[q * m + i].conj() * b_host[q * n + j])
+          .sum();
+        let want = alpha * ab + beta;
+        let x = got[i * n + j];
+        assert!(
+          (x - want).norm() < 1e-4,
+          "({i},{j}): got {x}, expect {want}"
+        );
+      }
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn gemm_shape_display() {
+    let shape = level3::GemmShape {
+      layout: CLBlastLayout::RowMajor,
+      a_transpose: CLBlastTranspose::No,
+      b_transpose: CLBlastTranspose::Yes,
+      m: 128,
+      n: 256,
+      k: 512,
+      a_ld: 512,
+      b_ld: 512,
+      c_ld: 256,
+    };
+    assert_eq!(
+      shape.to_string(),
+      "RowMajor N×T 128x256x512 (ld 512/512/256)"
+    );
+  }
+
+  #[test]
+  fn sgemm_rejects_dimension_above_i32_max() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::CLBlastError;
+
+    let queue = default_queue()?;
+    let buf = filled(&queue, &[0.0; 4])?;
+    let m = level3::MAX_DIMENSION + 1;
+    let result = level3::sgemm(
+      &queue,
+      CLBlastLayout::ColMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      m,
+      1,
+      1,
+      1.0,
+      &buf,
+      0,
+      m,
+      &buf,
+      0,
+      1,
+      0.0,
+      &buf,
+      0,
+      m,
+      &[],
+    );
+    assert!(matches!(result, Err(CLBlastError::DimensionTooLarge)));
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_on_sub_buffer_matches_standalone() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let n = 2usize;
+    // 1024 floats = 4 KiB keeps the sub-buffer origin aligned on any device.
+    let origin = 1024usize;
+    let a = filled(&queue, &[1.0, 2.0, 3.0, 4.0])?;
+    let b = filled(&queue, &[5.0, 6.0, 7.0, 8.0])?;
+    let c_alone = filled(&queue, &[0.0; 4])?;
+    let parent = filled(&queue, &vec![0.0; origin + n * n])?;
+    let c_sub = parent.create_sub_buffer(None, origin, n * n)?;
+
+    let run = |c: &Buffer<f32>, c_offset: usize| {
+      level3::sgemm(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        n,
+        n,
+        n,
+        1.0,
+        &a,
+        0,
+        n,
+        &b,
+        0,
+        n,
+        0.0,
+        c,
+        c_offset,
+        n,
+        &[],
+      )
+    };
+    run(&c_alone, 0)?;
+    // Offsets into a sub-buffer are relative to its origin.
+    run(&c_sub, 0)?;
+
+    let mut want = vec![0.0f32; n * n];
+    let mut got = vec![0.0f32; n * n];
+    c_alone.read(&mut want).enq()?;
+    c_sub.read(&mut got).enq()?;
+    assert_eq!(got, want);
+
+    // The same result lands in the parent at the sub-buffer origin.
+    let mut whole = vec![0.0f32; origin + n * n];
+    parent.read(&mut whole).enq()?;
+    assert_eq!(&whole[origin..], &want[..]);
+    Ok(())
+  }
+
+  #[cfg(feature = "complex")]
+  #[test]
+  fn cherk_hermitian_gram_matrix() -> Result<(), Box<dyn std::error::Error>> {
+    use clblast_binding::Complex32;
+
+    let queue = default_queue()?;
+    let (n, k) = (3usize, 2usize);
+    // `a` is k x n, row-major; C = A^H * A.
+    let a = [
+      Complex32::new(1.0, 1.0),
+      Complex32::new(2.0, 0.0),
+      Complex32::new(0.0, -1.0),
+      Complex32::new(3.0, -2.0),
+      Complex32::new(1.0, 1.0),
+      Complex32::new(-1.0, 2.0),
+    ];
+    let ab = Buffer::builder()
+      .queue(queue.clone())
+      .len(a.len())
+      .copy_host_slice(&a)
+      .build()?;
+    let cb = Buffer::<Complex32>::builder()
+      .queue(queue.clone())
+      .len(n * n)
+      .fill_val(Complex32::new(0.0, 0.0))
+      .build()?;
+
+    // `alpha` and `beta` are real even though the matrices are complex.
+    level3::cherk(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTriangle::Upper,
+      CLBlastTranspose::Conjugate,
+      n,
+      k,
+      1.0f32,
+      &ab,
+      0,
+      n,
+      0.0f32,
+      &cb,
+      0,
+      n,
+      &[],
+    )?;
+
+    let mut got = vec![Complex32::new(0.0, 0.0); n * n];
+    cb.read(&mut got).enq()?;
+    for i in 0..n {
+      for j in i..n {
+        let want: Complex32 = (0..k).map(|p| a[p * n + i].conj() * a[p * n + j]).sum();
+        let got = got[i * n + j];
+        assert!(
+          (got - want).norm() < 1e-4,
+          "c[{i}][{j}] = {got}, want {want}"
+        );
+        if i == j {
+          assert!(got.im.abs() < 1e-4, "diagonal c[{i}][{i}] = {got}");
+        }
+      }
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_temp_buffer_too_small_reports_needed_size() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    // Odd sizes push CLBlast onto its padded (indirect) path, which needs scratch.
+    let n = 257usize;
+    let size = level3::sgemm_temp_buffer_size(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      n,
+      n,
+      n,
+      0,
+      n,
+      0,
+      n,
+      0,
+      n,
+    )?;
+    if size <= 1 {
+      eprintln!("skipping: no scratch needed on this device");
+      return Ok(());
+    }
+    let filled = |v: f32| {
+      Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(n * n)
+        .fill_val(v)
+        .build()
+    };
+    let (a, b, c) = (filled(1.0)?, filled(2.0)?, filled(0.0)?);
+    let run = |temp: &Buffer<u8>| {
+      level3::sgemm_with_temp_buffer(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        n,
+        n,
+        n,
+        1.0,
+        &a,
+        0,
+        n,
+        &b,
+        0,
+        n,
+        0.0,
+        &c,
+        0,
+        n,
+        temp,
+        &[],
+      )
+    };
+
+    let tiny = Buffer::<u8>::builder()
+      .queue(queue.clone())
+      .len(1)
+      .build()?;
+    match run(&tiny) {
+      Err(CLBlastError::TempBufferTooSmall { needed }) => assert_eq!(needed, size),
+      other => panic!("expected TempBufferTooSmall, got {other:?}"),
+    }
+
+    let temp = Buffer::<u8>::builder()
+      .queue(queue.clone())
+      .len(size)
+      .build()?;
+    run(&temp)?;
+    let mut got = vec![0.0f32; n * n];
+    c.read(&mut got).enq()?;
+    assert!(got.iter().all(|&x| x == 2.0 * n as f32));
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_with_temp_allocates_or_reuses_scratch() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let n = 257usize;
+    let filled = |v: f32| {
+      Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(n * n)
+        .fill_val(v)
+        .build()
+    };
+    let (a, b, c) = (filled(1.0)?, filled(2.0)?, filled(0.0)?);
+    let run = |temp: Option<&Buffer<u8>>, beta: f32| {
+      level3::sgemm_with_temp(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        n,
+        n,
+        n,
+        1.0,
+        &a,
+        0,
+        n,
+        &b,
+        0,
+        n,
+        beta,
+        &c,
+        0,
+        n,
+        temp,
+        &[],
+      )
+    };
+
+    // The first call queries the scratch size, the second hits the cache.
+    let queries = level3::temp_size_queries();
+    run(None, 0.0)?;
+    assert_eq!(level3::temp_size_queries(), queries + 1);
+    run(None, 1.0)?;
+    assert_eq!(level3::temp_size_queries(), queries + 1);
+    let size = level3::sgemm_temp_buffer_size(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      n,
+      n,
+      n,
+      0,
+      n,
+      0,
+      n,
+      0,
+      n,
+    )?;
+    let temp = Buffer::<u8>::builder()
+      .queue(queue.clone())
+      .len(size.max(1))
+      .build()?;
+    run(Some(&temp), 1.0)?;
+
+    let mut got = vec![0.0f32; n * n];
+    c.read(&mut got).enq()?;
+    assert!(got.iter().all(|&x| x == 6.0 * n as f32));
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_chain_on_out_of_order_queue() -> Result<(), Box<dyn std::error::Error>> {
+    use ocl::flags::CommandQueueProperties;
+
+    let base = default_queue()?;
+    let Ok(queue) = Queue::new(
+      &base.context(),
+      base.device(),
+      Some(CommandQueueProperties::new().out_of_order()),
+    ) else {
+      eprintln!("skipping: out-of-order queues not supported");
+      return Ok(());
+    };
+    let n = 64usize;
+    let buffer = |v: f32| {
+      Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(n * n)
+        .fill_val(v)
+        .build()
+    };
+    let (a, b, c1, c2) = (buffer(1.0)?, buffer(1.0)?, buffer(0.0)?, buffer(0.0)?);
+    let gemm = |x: &Buffer<f32>, out: &Buffer<f32>, wait: &[ocl::core::Event]| {
+      level3::sgemm(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        n,
+        n,
+        n,
+        1.0,
+        x,
+        0,
+        n,
+        &b,
+        0,
+        n,
+        0.0,
+        out,
+        0,
+        n,
+        wait,
+      )
+    };
+
+    // c2 = (a * b) * b; the second product must see the first one's output.
+    let first = gemm(&a, &c1, &[])?.expect("no event");
+    let second = gemm(&c1, &c2, &[first])?.expect("no event");
+    let mut got = vec![0.0f32; n * n];
+    c2.read(&mut got).ewait(&second).enq()?;
+    assert!(got.iter().all(|&x| x == (n * n) as f32));
+    Ok(())
+  }
+
+  #[test]
+  fn sgemm_events_survive_create_and_drop_churn() -> Result<(), Box<dyn std::error::Error>> {
+    // Run under a leak checker (e.g. valgrind or an ASan-built driver) to
+    // catch over-released or leaked completion events.
+    let queue = default_queue()?;
+    let (n, iters) = (4usize, 4000usize);
+    let a = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n * n)
+      .fill_val(1.0)
+      .build()?;
+    let c = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n * n)
+      .fill_val(0.0)
+      .build()?;
+
+    // c += a * a each round; every event is cloned (retained), waited on
+    // through the next wait list and then dropped.
+    let mut prev: Option<ocl::core::Event> = None;
+    for _ in 0..iters {
+      let event = sgemm(
+        &queue,
+        CLBlastLayout::RowMajor,
+        CLBlastTranspose::No,
+        CLBlastTranspose::No,
+        n,
+        n,
+        n,
+        1.0,
+        &a,
+        Offset(0),
+        Ld(n),
+        &a,
+        Offset(0),
+        Ld(n),
+        1.0,
+        &c,
+        Offset(0),
+        Ld(n),
+        prev.iter(),
+      )?
+      .expect("no event");
+      // `event` itself drops at the end of the round.
+      prev = Some(event.clone());
+    }
+    let last = prev.expect("no event");
+    last.wait_for()?;
+
+    let mut got = vec![0.0f32; n * n];
+    c.read(&mut got).enq()?;
+    assert!(got.iter().all(|&x| x == (iters * n) as f32));
+    Ok(())
+  }
+}
@@ -0,0 +1,44 @@
+#[cfg(all(test, feature = "extra"))]
+mod tests {
+  use clblast_binding::{clblast_sys::CLBlastLayout, default_queue, level3_extra};
+  use ocl::Buffer;
+
+  #[test]
+  fn transpose_both_layouts() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let (m, n) = (2usize, 3usize);
+    // Stored as given in either layout; the transpose swaps the roles of
+    // the leading dimension.
+    let host = [1.0f32, 2.0, 3.0, 4.0, 5.0, 6.0];
+    let a = Buffer::builder()
+      .queue(queue.clone())
+      .len(host.len())
+      .copy_host_slice(&host)
+      .build()?;
+
+    for (layout, a_ld, want) in [
+      // Row-major 2x3 [[1,2,3],[4,5,6]] -> 3x2 [[1,4],[2,5],[3,6]].
+      (
+        CLBlastLayout::RowMajor,
+        n,
+        [1.0f32, 4.0, 2.0, 5.0, 3.0, 6.0],
+      ),
+      // Column-major 2x3 [[1,3,5],[2,4,6]] -> 3x2 [[1,2],[3,4],[5,6]].
+      (
+        CLBlastLayout::ColMajor,
+        m,
+        [1.0f32, 3.0, 5.0, 2.0, 4.0, 6.0],
+      ),
+    ] {
+      let (at, event) = level3_extra::transpose(&queue, layout, m, n, &a, a_ld, &[])?;
+      let mut got = vec![0.0f32; m * n];
+      let mut read = at.read(&mut got);
+      if let Some(event) = &event {
+        read = read.ewait(event);
+      }
+      read.enq()?;
+      assert_eq!(got, want, "{layout:?}");
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,73 @@
+#[cfg(all(test, feature = "level2"))]
+mod tests {
+  use clblast_binding::{
+    clblast_sys::{CLBlastLayout, CLBlastTriangle},
+    default_queue, packed,
+  };
+  use ocl::Buffer;
+
+  #[test]
+  fn sspmv_matches_dense_symmetric() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let n = 3usize;
+    let dense = [
+      [4.0f32, 1.0, 2.0], //
+      [1.0, 5.0, 3.0],
+      [2.0, 3.0, 6.0],
+    ];
+    // Column-major upper packing: (i, j) with i <= j at i + j * (j + 1) / 2.
+    let mut ap = vec![0.0f32; packed::packed_len(n)];
+    for j in 0..n {
+      for i in 0..=j {
+        ap[i + j * (j + 1) / 2] = dense[i][j];
+      }
+    }
+    let x = [1.0f32, 2.0, 3.0];
+
+    let ap_buf = Buffer::builder()
+      .queue(queue.clone())
+      .len(ap.len())
+      .copy_host_slice(&ap)
+      .build()?;
+    let x_buf = Buffer::builder()
+      .queue(queue.clone())
+      .len(n)
+      .copy_host_slice(&x)
+      .build()?;
+    let y_buf = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n)
+      .fill_val(0.0)
+      .build()?;
+
+    packed::sspmv(
+      &queue,
+      CLBlastLayout::ColMajor,
+      CLBlastTriangle::Upper,
+      n,
+      1.0,
+      &ap_buf,
+      0,
+      &x_buf,
+      0,
+      1,
+      0.0,
+      &y_buf,
+      0,
+      1,
+      &[],
+    )?;
+
+    let mut got = vec![0.0f32; n];
+    y_buf.read(&mut got).enq()?;
+    for i in 0..n {
+      let want: f32 = (0..n).map(|j| dense[i][j] * x[j]).sum();
+      assert!(
+        (got[i] - want).abs() < 1e-4,
+        "y[{i}]: got {}, expect {want}",
+        got[i]
+      );
+    }
+    Ok(())
+  }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+  use clblast_binding::clblast_sys::CLBlastStatusCode;
+  use clblast_binding::error::{CLBlastError, classify_status};
+
+  #[test]
+  fn try_from_status_matches_check() {
+    assert!(CLBlastError::try_from_status(CLBlastStatusCode::Success).is_ok());
+    assert!(matches!(
+      CLBlastError::try_from_status(CLBlastStatusCode::InvalidLeadDimA),
+      Err(CLBlastError::InvalidLeadDimA)
+    ));
+    assert!(matches!(
+      CLBlastError::try_from_status(CLBlastStatusCode::InvalidLocalMemUsage),
+      Err(CLBlastError::InvalidLocalMemUsage)
+    ));
+  }
+
+  #[test]
+  fn status_code_round_trips() {
+    for code in [
+      CLBlastStatusCode::InvalidLeadDimB,
+      CLBlastStatusCode::NoDoublePrecision,
+      CLBlastStatusCode::InvalidLocalMemUsage,
+    ] {
+      let err = CLBlastError::try_from_status(code).unwrap_err();
+      assert_eq!(err.status_code(), Some(code));
+      assert_eq!(err.as_i32(), code as i32);
+    }
+    let err = CLBlastError::TempBufferTooSmall { needed: 64 };
+    assert_eq!(err.as_i32(), -2050);
+    assert_eq!(CLBlastError::DimensionTooLarge.status_code(), None);
+    assert_eq!(CLBlastError::DimensionTooLarge.as_i32(), -2040);
+  }
+
+  #[test]
+  fn every_status_has_a_named_variant() {
+    for &(code, name) in clblast_binding::all_status_codes() {
+      if code == CLBlastStatusCode::Success {
+        continue;
+      }
+      let err = CLBlastError::from(code);
+      assert_eq!(err.status_code(), Some(code));
+      assert_eq!(err.as_i32(), code as i32);
+      if let CLBlastError::OpenCL(raw) = err {
+        assert!((-999..0).contains(&raw), "{name} = {raw}");
+      } else {
+        assert_eq!(err.to_string(), name);
+      }
+    }
+    assert!(matches!(
+      CLBlastError::from_raw(-1024),
+      CLBlastError::NotImplemented
+    ));
+    assert!(matches!(
+      CLBlastError::from_raw(-7777),
+      CLBlastError::Unknown(-7777)
+    ));
+  }
+
+  #[test]
+  fn classify_status_splits_opencl_and_clblast_ranges() {
+    let oom = classify_status(-5);
+    assert!(matches!(oom, CLBlastError::OpenCL(-5)));
+    assert_eq!(oom.to_string(), "CL_OUT_OF_RESOURCES (-5)");
+    assert!(matches!(
+      classify_status(CLBlastStatusCode::DatabaseError as i32),
+      CLBlastError::DatabaseError
+    ));
+    assert!(matches!(
+      classify_status(CLBlastStatusCode::InvalidBufferSize as i32),
+      CLBlastError::InvalidBufferSize
+    ));
+    assert!(matches!(classify_status(0), CLBlastError::Unknown(0)));
+  }
+}
@@ -0,0 +1,280 @@
+#[cfg(all(test, feature = "level3"))]
+mod common;
+
+#[cfg(all(test, feature = "level3"))]
+mod tests {
+  use std::thread;
+  use std::time::Duration;
+
+  use clblast_binding::{
+    AutoWait, BlasPool, CLBlastError, Offset, WaitOnDrop, builder,
+    clblast_sys::{CLBlastLayout, CLBlastTranspose},
+    default_queue, profile, sync,
+  };
+  use ocl::core::{CommandExecutionStatus, Event as CoreEvent};
+  use ocl::flags::CommandQueueProperties;
+  use ocl::{Buffer, Queue};
+
+  use crate::common::profiling_queue;
+
+  #[test]
+  fn profile_reports_nonzero_gemm_time() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(queue) = profiling_queue()? else {
+      eprintln!("skipping: profiling queues not supported");
+      return Ok(());
+    };
+    let n = 512usize;
+    let a = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n * n)
+      .fill_val(1.0)
+      .build()?;
+    let b = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n * n)
+      .fill_val(1.0)
+      .build()?;
+    let c = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n * n)
+      .fill_val(0.0)
+      .build()?;
+
+    let event = builder::sgemm_auto(
+      &queue,
+      CLBlastLayout::RowMajor,
+      CLBlastTranspose::No,
+      CLBlastTranspose::No,
+      n,
+      n,
+      n,
+      1.0,
+      &a,
+      &b,
+      0.0,
+      &c,
+      &[],
+    )?
+    .expect("sgemm returned no event");
+    let elapsed = profile(&event)?;
+    assert!(!elapsed.is_zero(), "elapsed = {elapsed:?}");
+    Ok(())
+  }
+
+  #[test]
+  fn assume_in_order_keeps_same_queue_ordering() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+
+    // Safety: both calls go to the same in-order queue.
+    unsafe { sync::assume_in_order(true) };
+    assert!(sync::assumes_in_order());
+    let first = clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, &[])?.expect("no event");
+    let second =
+      clblast_binding::sscal(&queue, 4, 3.0, &x, Offset(0), 1, [&first])?.expect("no event");
+    unsafe { sync::assume_in_order(false) };
+
+    let mut got = vec![0.0f32; 4];
+    x.read(&mut got).ewait(&second).enq()?;
+    assert_eq!(got, [6.0; 4]);
+    Ok(())
+  }
+
+  #[test]
+  fn blas_pool_runs_on_every_device() -> Result<(), Box<dyn std::error::Error>> {
+    let pool = BlasPool::new(&default_queue()?.context())?;
+    assert_eq!(pool.devices().len(), pool.len());
+    assert!(std::ptr::eq(pool.next(), pool.get(0).unwrap()));
+
+    // Buffers live on the device of the `Blas` they are used with.
+    let buffers = pool.for_each(|i, blas| {
+      Buffer::<f32>::builder()
+        .queue(blas.queue().clone())
+        .len(4)
+        .fill_val(i as f32 + 1.0)
+        .build()
+    });
+    let buffers = buffers.into_iter().collect::<ocl::Result<Vec<_>>>()?;
+    let events = pool.for_each(|i, blas| blas.sscal(4, 2.0, &buffers[i], 0, 1, &[]));
+    for (i, event) in events.into_iter().enumerate() {
+      let mut got = vec![0.0f32; 4];
+      let mut read = buffers[i].read(&mut got);
+      if let Some(event) = event? {
+        read = read.ewait(&event);
+      }
+      read.enq()?;
+      assert_eq!(got, [2.0 * (i as f32 + 1.0); 4]);
+    }
+    Ok(())
+  }
+
+  #[test]
+  fn wrappers_survive_queue_cache_eviction_and_release() -> Result<(), Box<dyn std::error::Error>> {
+    use ocl::flags::CommandQueueProperties;
+
+    let base = default_queue()?;
+    // More queues than the per-thread cache keeps, all out of order.
+    for round in 0..10 {
+      let Ok(queue) = Queue::new(
+        &base.context(),
+        base.device(),
+        Some(CommandQueueProperties::new().out_of_order()),
+      ) else {
+        eprintln!("skipping: out-of-order queues not supported");
+        return Ok(());
+      };
+      let x = Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(4)
+        .fill_val(1.0)
+        .build()?;
+      let first = clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, &[])?.expect("no event");
+      let second =
+        clblast_binding::sscal(&queue, 4, 3.0, &x, Offset(0), 1, [&first])?.expect("no event");
+      let mut got = vec![0.0f32; 4];
+      x.read(&mut got).ewait(&second).enq()?;
+      assert_eq!(got, [6.0; 4], "round {round}");
+      if round % 4 == 3 {
+        sync::release_queue_cache();
+      }
+    }
+    sync::release_queue_cache();
+    Ok(())
+  }
+
+  #[test]
+  fn wrappers_return_an_event_without_wait_list() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+    let event = clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, &[])?;
+    let event = event.expect("successful submit returned no event");
+    event.wait_for()?;
+    clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, &[])?;
+    sync::finish(&queue)?;
+
+    let mut got = vec![0.0f32; 4];
+    x.read(&mut got).enq()?;
+    assert_eq!(got, [4.0; 4]);
+    Ok(())
+  }
+
+  /// A user event on `queue`'s context, completed from another thread
+  /// after a short delay.
+  fn delayed_gate(queue: &Queue) -> ocl::core::Result<CoreEvent> {
+    let gate = ocl::core::create_user_event(queue.context().as_core())?;
+    let opener = gate.clone();
+    thread::spawn(move || {
+      thread::sleep(Duration::from_millis(50));
+      ocl::core::set_user_event_status(&opener, CommandExecutionStatus::Complete)
+        .expect("complete the user event");
+    });
+    Ok(gate)
+  }
+
+  #[test]
+  fn auto_wait_blocks_on_drop() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+    // The scal cannot run before the gate opens, so it is only complete
+    // here if dropping the guard waited for it.
+    let gate = delayed_gate(&queue)?;
+    let event = clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, [&gate])?;
+    let probe = event.clone().expect("no event");
+    drop(event.auto_wait(&queue));
+    assert!(probe.is_complete()?);
+    Ok(())
+  }
+
+  #[test]
+  fn wait_on_drop_without_event_finishes_the_queue() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+    let gate = delayed_gate(&queue)?;
+    let probe =
+      clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, [&gate])?.expect("no event");
+    let guard = WaitOnDrop::new(None, &queue);
+    assert!(guard.event().is_none());
+    drop(guard);
+    assert!(probe.is_complete()?);
+    Ok(())
+  }
+
+  /// An out-of-order queue with `extra` properties, or `None` when the
+  /// device refuses one.
+  fn out_of_order_queue(extra: CommandQueueProperties) -> ocl::Result<Option<Queue>> {
+    let queue = default_queue()?;
+    Ok(Queue::new(&queue.context(), queue.device(), Some(extra.out_of_order())).ok())
+  }
+
+  #[test]
+  fn finish_waits_for_the_scratch_queue() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(queue) = out_of_order_queue(CommandQueueProperties::new())? else {
+      eprintln!("skipping: out-of-order queues not supported");
+      return Ok(());
+    };
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(4)
+      .fill_val(1.0)
+      .build()?;
+    let gate = delayed_gate(&queue)?;
+    let probe =
+      clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, [&gate])?.expect("no event");
+    sync::finish(&queue)?;
+    assert!(probe.is_complete()?);
+
+    let gate = delayed_gate(&queue)?;
+    let probe =
+      clblast_binding::sscal(&queue, 4, 2.0, &x, Offset(0), 1, [&gate])?.expect("no event");
+    drop(WaitOnDrop::new(None, &queue));
+    assert!(probe.is_complete()?);
+    Ok(())
+  }
+
+  #[test]
+  fn profile_works_on_an_out_of_order_profiling_queue() -> Result<(), Box<dyn std::error::Error>> {
+    let Some(queue) = out_of_order_queue(CommandQueueProperties::new().profiling())? else {
+      eprintln!("skipping: out-of-order profiling queues not supported");
+      return Ok(());
+    };
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(1 << 16)
+      .fill_val(1.0)
+      .build()?;
+    let event =
+      clblast_binding::sscal(&queue, 1 << 16, 2.0, &x, Offset(0), 1, &[])?.expect("no event");
+    profile(&event)?;
+    Ok(())
+  }
+
+  #[test]
+  fn wait_reports_a_failed_event() -> Result<(), Box<dyn std::error::Error>> {
+    let queue = default_queue()?;
+    let failed = ocl::core::create_user_event(queue.context().as_core())?;
+    // Safety: `failed` is a live user event; a negative status marks it as
+    // terminated with an error.
+    let err = unsafe { cl_sys::clSetUserEventStatus(*failed.as_ptr_ref(), -1) };
+    assert_eq!(err, cl_sys::CL_SUCCESS);
+
+    let result = WaitOnDrop::new(Some(failed), &queue).wait();
+    assert!(matches!(result, Err(CLBlastError::Ocl(_))), "{result:?}");
+    Ok(())
+  }
+}
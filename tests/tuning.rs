@@ -0,0 +1,137 @@
+#[cfg(all(test, feature = "level2", feature = "level3"))]
+mod tests {
+  use std::collections::HashMap;
+  use std::sync::{Mutex, MutexGuard};
+
+  use clblast_binding::enums::{Layout, Precision, Transpose};
+  use clblast_binding::error::CLBlastError;
+  use clblast_binding::tuning::WarmupRoutine;
+  use clblast_binding::{Offset, caps, default_queue, tuning};
+  use ocl::Buffer;
+
+  /// CLBlast's kernel and parameter caches are process-wide; tests that
+  /// change or clear them hold this so they never overlap.
+  static CACHE: Mutex<()> = Mutex::new(());
+
+  fn lock_cache() -> MutexGuard<'static, ()> {
+    CACHE
+      .lock()
+      .unwrap_or_else(|poisoned| poisoned.into_inner())
+  }
+
+  #[test]
+  fn saxpy_is_correct_with_overridden_parameters() -> Result<(), Box<dyn std::error::Error>> {
+    let _cache = lock_cache();
+    let queue = default_queue()?;
+    let device = queue.device();
+    let params: HashMap<String, usize> = [("WGS", 64), ("WPT", 1), ("VW", 1)]
+      .into_iter()
+      .map(|(name, value)| (name.to_string(), value))
+      .collect();
+    tuning::override_parameters(&device, "Xaxpy", Precision::Single, &params)?;
+
+    let n = 100;
+    let x = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n)
+      .fill_val(1.0)
+      .build()?;
+    let y = Buffer::<f32>::builder()
+      .queue(queue.clone())
+      .len(n)
+      .fill_val(2.0)
+      .build()?;
+    let event = clblast_binding::saxpy(&queue, n, 3.0, &x, Offset(0), 1, &y, Offset(0), 1, &[])?
+      .expect("no event");
+    let mut got = vec![0.0f32; n];
+    y.read(&mut got).ewait(&event).enq()?;
+    assert_eq!(got, vec![5.0; n]);
+
+    // Drop the override so it cannot leak into later tests.
+    tuning::clear_cache()?;
+    Ok(())
+  }
+
+  #[test]
+  fn override_parameters_rejects_bad_names() -> Result<(), Box<dyn std::error::Error>> {
+    let device = default_queue()?.device();
+    let params = HashMap::from([("WGS".to_string(), 64)]);
+    assert!(matches!(
+      tuning::override_parameters(&device, "NoSuchKernel", Precision::Single, &params),
+      Err(CLBlastError::InvalidOverrideKernel)
+    ));
+    assert!(matches!(
+      tuning::override_parameters(&device, "Xaxpy\0", Precision::Single, &params),
+      Err(CLBlastError::InvalidOverrideKernel)
+    ));
+    let params = HashMap::from([("W\0GS".to_string(), 64)]);
+    assert!(matches!(
+      tuning::override_parameters(&device, "Xaxpy", Precision::Single, &params),
+      Err(CLBlastError::MissingOverrideParameter)
+    ));
+    Ok(())
+  }
+
+  #[test]
+  fn gemm_runs_after_fill_cache() -> Result<(), Box<dyn std::error::Error>> {
+    let _cache = lock_cache();
+    let queue = default_queue()?;
+    tuning::fill_cache(&queue.device())?;
+
+    let n = 64;
+    let buffer = |value: f32| {
+      Buffer::<f32>::builder()
+        .queue(queue.clone())
+        .len(n * n)
+        .fill_val(value)
+        .build()
+    };
+    let (a, b, c) = (buffer(1.0)?, buffer(2.0)?, buffer(0.0)?);
+    clblast_binding::level3::sgemm(
+      &queue,
+      Layout::RowMajor,
+      Transpose::No,
+      Transpose::No,
+      n,
+      n,
+      n,
+      1.0,
+      &a,
+      0,
+      n,
+      &b,
+      0,
+      n,
+      0.0,
+      &c,
+      0,
+      n,
+      &[],
+    )?;
+    queue.finish()?;
+
+    let mut got = vec![0.0f32; n * n];
+    c.read(&mut got).enq()?;
+    assert!(got.iter().all(|&x| x == 2.0 * n as f32));
+
+    tuning::clear_cache()?;
+    Ok(())
+  }
+
+  #[test]
+  fn warmup_runs_every_supported_precision() -> Result<(), Box<dyn std::error::Error>> {
+    let _cache = lock_cache();
+    let queue = default_queue()?;
+    let routines = [WarmupRoutine::Gemm, WarmupRoutine::Gemv];
+    tuning::warmup(&queue, &[Precision::Single], &routines)?;
+
+    // Every branch of `warmup` the device can run: `Half` and the
+    // double-precision ones only when reported.
+    let supported = caps::supported_precisions(&queue.device())?;
+    if !supported.contains(&Precision::Half) {
+      eprintln!("skipping Half: no cl_khr_fp16");
+    }
+    tuning::warmup(&queue, &supported, &routines)?;
+    Ok(())
+  }
+}